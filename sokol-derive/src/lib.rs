@@ -0,0 +1,426 @@
+/*!
+
+Derive macros for the [sokol](https://crates.io/crates/sokol) Rust bindings. Use them
+through the `derive` feature of the `sokol` crate, which re-exports them in `sokol::gfx`.
+
+# `#[derive(Vertex)]`
+
+Implements `sokol::gfx::SgVertex` for a `#[repr(C)]` struct. Each field becomes a vertex
+attribute, in declaration order, with its offset in the struct and the vertex format of its
+type (see `sokol::gfx::SgVertexAttr`). The stride is the size of the struct.
+
+Struct attributes:
+
+- `#[vertex(per_instance)]` - the buffer is stepped per instance instead of per vertex.
+- `#[vertex(per_instance, step_rate = N)]` - advances every `N` instances.
+
+Field attributes:
+
+- `#[vertex(format = Name)]` - uses `SgVertexFormat::Name` instead of the default format of
+  the field's type. Its size is checked against the field at compile time.
+- `#[vertex(skip)]` - doesn't create an attribute for this field, e.g. for padding.
+
+# `#[derive(UniformBlock)]`
+
+Implements `sokol::gfx::SgUniformBlock` for a `#[repr(C)]` struct. Each field becomes a
+uniform, in declaration order, with the uniform type of the field's type (see
+`sokol::gfx::SgUniformField`). The field offsets and the struct size are checked at compile
+time against the uniform layout sokol_gfx expects.
+
+Struct attributes:
+
+- `#[uniform_block(std140)]` - uses `SgUniformLayout::Std140`, the default is `Native`.
+
+Field attributes:
+
+- `#[uniform(array)]` - the field is an array `[T; N]` of `N` uniforms of type `T`.
+- `#[uniform(name = "...")]` - the uniform name, defaults to the field name.
+- `#[uniform(skip)]` - doesn't create a uniform for this field, e.g. for explicit padding.
+  The field must directly follow the previous uniform, and still counts towards the
+  offsets of the following ones.
+
+*/
+
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, LitInt, LitStr, Member, Result, Type};
+
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    match vertex(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(UniformBlock, attributes(uniform_block, uniform))]
+pub fn derive_uniform_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    match uniform_block(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct VertexAttrs {
+    per_instance: bool,
+    step_rate: Option<LitInt>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    format: Option<Ident>,
+    skip: bool,
+}
+
+#[derive(Default)]
+struct UniformBlockAttrs {
+    std140: bool,
+}
+
+#[derive(Default)]
+struct UniformAttrs {
+    array: bool,
+    name: Option<LitStr>,
+    skip: bool,
+}
+
+fn vertex(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let members = struct_members(input, "Vertex")?;
+    let attrs = parse_vertex_attrs(input)?;
+
+    let mut attr_descs = Vec::new();
+    let mut size_checks = Vec::new();
+
+    for (member, field) in &members {
+        let field_attrs = parse_field_attrs(field)?;
+
+        if field_attrs.skip {
+            continue;
+        }
+
+        let ty = &field.ty;
+
+        let format = match &field_attrs.format {
+            Some(format) => {
+                let message = LitStr::new(
+                    &format!("SgVertexFormat::{} doesn't match the size of field `{}`", format, quote!(#member)),
+                    format.span(),
+                );
+
+                size_checks.push(quote! {
+                    assert!(::sokol::gfx::SgVertexFormat::#format.size() == ::std::mem::size_of::<#ty>(), #message);
+                });
+
+                quote!(::sokol::gfx::SgVertexFormat::#format)
+            }
+            None => quote!(<#ty as ::sokol::gfx::SgVertexAttr>::FORMAT),
+        };
+
+        attr_descs.push(quote! {
+            ::sokol::gfx::SgVertexAttrDesc {
+                buffer_index,
+                offset: ::std::mem::offset_of!(#name, #member) as i32,
+                format: #format,
+            }
+        });
+    }
+
+    if attr_descs.is_empty() {
+        return Err(Error::new(input.span(), "#[derive(Vertex)] requires at least one vertex attribute"));
+    }
+
+    let step = if attrs.per_instance {
+        let step_rate = attrs.step_rate.map_or_else(|| quote!(1), |rate| quote!(#rate));
+
+        quote! {
+            const STEP_FUNC: ::sokol::gfx::SgVertexStep = ::sokol::gfx::SgVertexStep::PerInstance;
+            const STEP_RATE: i32 = #step_rate;
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        const _: () = {
+            #(#size_checks)*
+        };
+
+        impl ::sokol::gfx::SgVertex for #name {
+            #step
+
+            fn attrs(buffer_index: i32) -> ::std::vec::Vec<::sokol::gfx::SgVertexAttrDesc> {
+                vec![#(#attr_descs),*]
+            }
+        }
+    })
+}
+
+fn uniform_block(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let members = struct_members(input, "UniformBlock")?;
+    let attrs = parse_uniform_block_attrs(input)?;
+
+    let layout = if attrs.std140 {
+        quote!(::sokol::gfx::SgUniformLayout::Std140)
+    } else {
+        quote!(::sokol::gfx::SgUniformLayout::Native)
+    };
+
+    let mut uniform_descs = Vec::new();
+    let mut layout_checks = Vec::new();
+
+    for (member, field) in &members {
+        let uniform_attrs = parse_uniform_attrs(field)?;
+        let ty = &field.ty;
+        let field_name = quote!(#member).to_string();
+
+        if uniform_attrs.skip {
+            let skip_message = LitStr::new(
+                &format!("skipped field `{}` doesn't directly follow the previous uniform", field_name),
+                ty.span(),
+            );
+
+            layout_checks.push(quote! {
+                assert!(::std::mem::offset_of!(#name, #member) == offset, #skip_message);
+
+                offset += ::std::mem::size_of::<#ty>();
+            });
+
+            continue;
+        }
+
+        let (elem_ty, array_count) = if uniform_attrs.array {
+            match ty {
+                Type::Array(array) => {
+                    let elem = &array.elem;
+                    let len = &array.len;
+                    (quote!(#elem), quote!((#len) as i32))
+                }
+                _ => return Err(Error::new(ty.span(), "#[uniform(array)] requires an array type")),
+            }
+        } else {
+            (quote!(#ty), quote!(1))
+        };
+
+        let uniform_name = match uniform_attrs.name {
+            Some(name) => name,
+            None => LitStr::new(&field_name, member.span()),
+        };
+
+        let array_message = LitStr::new(
+            &format!("the uniform layout doesn't support arrays of the type of field `{}`", field_name),
+            ty.span(),
+        );
+        let size_message = LitStr::new(
+            &format!("the size of field `{}` doesn't match its uniform type", field_name),
+            ty.span(),
+        );
+        let offset_message = LitStr::new(
+            &format!("field `{}` isn't aligned as required by the uniform layout", field_name),
+            ty.span(),
+        );
+
+        layout_checks.push(quote! {
+            {
+                let uniform_type = <#elem_ty as ::sokol::gfx::SgUniformField>::UNIFORM_TYPE;
+                let array_count: i32 = #array_count;
+
+                assert!(array_count <= 1 || layout.supports_array(uniform_type), #array_message);
+                assert!(layout.uniform_size(uniform_type, array_count) == ::std::mem::size_of::<#ty>(), #size_message);
+
+                let align = layout.uniform_align(uniform_type, array_count);
+                offset = (offset + align - 1) / align * align;
+
+                assert!(::std::mem::offset_of!(#name, #member) == offset, #offset_message);
+
+                offset += layout.uniform_size(uniform_type, array_count);
+            }
+        });
+
+        uniform_descs.push(quote! {
+            ::sokol::gfx::SgShaderUniformDesc {
+                name: #uniform_name,
+                uniform_type: <#elem_ty as ::sokol::gfx::SgUniformField>::UNIFORM_TYPE,
+                array_count: #array_count,
+            }
+        });
+    }
+
+    if uniform_descs.is_empty() {
+        return Err(Error::new(input.span(), "#[derive(UniformBlock)] requires at least one uniform"));
+    }
+
+    let block_message = LitStr::new(
+        &format!("the size of `{}` doesn't match the uniform layout, check for padding", name),
+        name.span(),
+    );
+
+    Ok(quote! {
+        #[allow(unused_assignments)]
+        const _: () = {
+            let layout = #layout;
+            let mut offset = 0usize;
+
+            #(#layout_checks)*
+
+            assert!(::std::mem::size_of::<#name>() == layout.block_size(offset), #block_message);
+        };
+
+        impl ::sokol::gfx::SgUniformBlock for #name {
+            const LAYOUT: ::sokol::gfx::SgUniformLayout = #layout;
+
+            fn uniforms() -> ::std::vec::Vec<::sokol::gfx::SgShaderUniformDesc<'static>> {
+                vec![#(#uniform_descs),*]
+            }
+        }
+    })
+}
+
+/// Checks the requirements shared by all derives, and returns the struct's fields.
+fn struct_members<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<(Member, &'a Field)>> {
+    if !input.generics.params.is_empty() {
+        let message = format!("#[derive({})] doesn't support generic structs", derive);
+        return Err(Error::new(input.generics.span(), message));
+    }
+
+    check_repr_c(input, derive)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(Error::new(input.span(), format!("#[derive({})] only supports structs", derive))),
+    };
+
+    let members = match fields {
+        Fields::Named(fields) => fields.named.iter()
+            .map(|f| (Member::Named(f.ident.clone().unwrap()), f))
+            .collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter()
+            .enumerate()
+            .map(|(i, f)| (Member::Unnamed(i.into()), f))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    Ok(members)
+}
+
+fn check_repr_c(input: &DeriveInput, derive: &str) -> Result<()> {
+    let mut repr_c = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            // skip the arguments of e.g. `align(16)`
+            if meta.input.peek(syn::token::Paren) {
+                let args;
+                syn::parenthesized!(args in meta.input);
+                args.parse::<TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    if repr_c {
+        Ok(())
+    } else {
+        Err(Error::new(input.ident.span(), format!("#[derive({})] requires #[repr(C)]", derive)))
+    }
+}
+
+fn parse_vertex_attrs(input: &DeriveInput) -> Result<VertexAttrs> {
+    let mut attrs = VertexAttrs::default();
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("vertex")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("per_instance") {
+                attrs.per_instance = true;
+                Ok(())
+            } else if meta.path.is_ident("step_rate") {
+                attrs.step_rate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `per_instance` or `step_rate`"))
+            }
+        })?;
+    }
+
+    if let (false, Some(step_rate)) = (attrs.per_instance, &attrs.step_rate) {
+        return Err(Error::new(step_rate.span(), "`step_rate` requires `per_instance`"));
+    }
+
+    Ok(attrs)
+}
+
+fn parse_field_attrs(field: &Field) -> Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("vertex")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                attrs.format = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `format` or `skip`"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
+fn parse_uniform_block_attrs(input: &DeriveInput) -> Result<UniformBlockAttrs> {
+    let mut attrs = UniformBlockAttrs::default();
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("uniform_block")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("std140") {
+                attrs.std140 = true;
+                Ok(())
+            } else if meta.path.is_ident("native") {
+                attrs.std140 = false;
+                Ok(())
+            } else {
+                Err(meta.error("expected `std140` or `native`"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
+fn parse_uniform_attrs(field: &Field) -> Result<UniformAttrs> {
+    let mut attrs = UniformAttrs::default();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("uniform")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("array") {
+                attrs.array = true;
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                attrs.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `array`, `name` or `skip`"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
@@ -1,48 +1,105 @@
+use std::cell::RefCell;
 use std::os::raw::c_char;
 use std::ptr::null;
 
+use sokol::app::ffi::SAppStrings;
 use sokol::app::SAppEvent;
 use sokol::gfx::SgPixelFormat;
 use sys::imgui::ffi::*;
 
 pub mod ffi {
-    use std::ffi::CString;
     use std::os::raw::{c_char, c_int};
 
-    use sokol::app::ffi::SAppEvent;
+    use sokol::app::ffi::{SAppAllocator, SAppEvent, SAppLogger, SAppStrings};
     use sokol::gfx::SgPixelFormat;
 
     #[repr(C)]
     #[derive(Debug)]
     pub struct SImGuiDesc {
-        max_vertices: i32,
+        max_vertices: c_int,
         color_format: SgPixelFormat,
         depth_format: SgPixelFormat,
-        sample_count: i32,
-        dpi_scale: f32,
+        sample_count: c_int,
         ini_filename: *const c_char,
         no_default_font: bool,
+        disable_paste_override: bool,
+        disable_set_mouse_cursor: bool,
+        disable_windows_resize_from_edges: bool,
+        write_alpha_channel: bool,
+        allocator: SAppAllocator,
+        logger: SAppLogger,
     }
 
     impl SImGuiDesc {
-        pub fn make(desc: super::SImGuiDesc) -> Self {
-            let ini_filename = CString::new(desc.ini_filename).unwrap();
-
+        pub fn make(desc: super::SImGuiDesc, strings: &mut SAppStrings) -> Self {
             SImGuiDesc {
                 max_vertices: desc.max_vertices,
                 color_format: desc.color_format,
                 depth_format: desc.depth_format,
                 sample_count: desc.sample_count,
-                dpi_scale: desc.dpi_scale,
-                ini_filename: ini_filename.into_raw(),
+                ini_filename: strings.add_or_default(desc.ini_filename),
                 no_default_font: desc.no_default_font,
+                disable_paste_override: desc.disable_paste_override,
+                disable_set_mouse_cursor: desc.disable_set_mouse_cursor,
+                disable_windows_resize_from_edges: desc.disable_windows_resize_from_edges,
+                write_alpha_channel: desc.write_alpha_channel,
+                allocator: Default::default(),
+                logger: Default::default(),
+            }
+        }
+    }
+
+    #[repr(C)]
+    #[derive(Debug)]
+    pub struct SImGuiFrameDesc {
+        width: c_int,
+        height: c_int,
+        delta_time: f64,
+        dpi_scale: f32,
+    }
+
+    impl SImGuiFrameDesc {
+        pub fn make(desc: &super::SImGuiFrameDesc) -> Self {
+            SImGuiFrameDesc {
+                width: desc.width,
+                height: desc.height,
+                delta_time: desc.delta_time,
+                dpi_scale: desc.dpi_scale,
             }
         }
     }
 
+    mod layout {
+        use sys::raw;
+
+        use super::*;
+
+        sokol::assert_layout!(SImGuiDesc, raw::simgui_desc_t, {
+            max_vertices => max_vertices,
+            color_format => color_format,
+            depth_format => depth_format,
+            sample_count => sample_count,
+            ini_filename => ini_filename,
+            no_default_font => no_default_font,
+            disable_paste_override => disable_paste_override,
+            disable_set_mouse_cursor => disable_set_mouse_cursor,
+            disable_windows_resize_from_edges => disable_windows_resize_from_edges,
+            write_alpha_channel => write_alpha_channel,
+            allocator => allocator,
+            logger => logger,
+        });
+
+        sokol::assert_layout!(SImGuiFrameDesc, raw::simgui_frame_desc_t, {
+            width => width,
+            height => height,
+            delta_time => delta_time,
+            dpi_scale => dpi_scale,
+        });
+    }
+
     extern {
         pub fn simgui_setup(desc: *const SImGuiDesc);
-        pub fn simgui_new_frame(width: c_int, height: c_int, delta_time: f64);
+        pub fn simgui_new_frame(desc: *const SImGuiFrameDesc);
         pub fn simgui_render();
         pub fn simgui_handle_event(event: *const SAppEvent) -> bool;
         pub fn simgui_shutdown();
@@ -55,20 +112,42 @@ pub struct SImGuiDesc<'a> {
     pub color_format: SgPixelFormat,
     pub depth_format: SgPixelFormat,
     pub sample_count: i32,
-    pub dpi_scale: f32,
+    /// The file Dear ImGui saves its window settings to. An empty string is passed as
+    /// NULL, which disables the .ini file.
     pub ini_filename: &'a str,
     pub no_default_font: bool,
+    pub disable_paste_override: bool,
+    pub disable_set_mouse_cursor: bool,
+    pub disable_windows_resize_from_edges: bool,
+    pub write_alpha_channel: bool,
+}
+
+#[derive(Default, Debug)]
+pub struct SImGuiFrameDesc {
+    pub width: i32,
+    pub height: i32,
+    pub delta_time: f64,
+    pub dpi_scale: f32,
+}
+
+thread_local! {
+    /// Dear ImGui keeps a pointer to the .ini filename until `simgui_shutdown()`.
+    static STRINGS: RefCell<SAppStrings> = RefCell::new(SAppStrings::default());
 }
 
 pub fn simgui_setup(desc: SImGuiDesc) {
+    let mut strings = SAppStrings::default();
+
     unsafe {
-        ffi::simgui_setup(&ffi::SImGuiDesc::make(desc));
+        ffi::simgui_setup(&ffi::SImGuiDesc::make(desc, &mut strings));
     }
+
+    STRINGS.with(|s| *s.borrow_mut() = strings);
 }
 
-pub fn simgui_new_frame(width: i32, height: i32, delta_time: f64) {
+pub fn simgui_new_frame(desc: &SImGuiFrameDesc) {
     unsafe {
-        ffi::simgui_new_frame(width, height, delta_time);
+        ffi::simgui_new_frame(&ffi::SImGuiFrameDesc::make(desc));
     }
 }
 
@@ -88,6 +167,8 @@ pub fn simgui_shutdown() {
     unsafe {
         ffi::simgui_shutdown();
     }
+
+    STRINGS.with(|s| *s.borrow_mut() = SAppStrings::default());
 }
 
 pub fn imgui_begin_main_menu_bar() -> bool {
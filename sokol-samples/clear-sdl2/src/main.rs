@@ -0,0 +1,80 @@
+//! Same as clear-sapp, but the window, GL context and event loop are owned by SDL2
+//! instead of sokol_app.
+//!
+//! This needs the SDL2 development libraries, so it is only built with the `sdl2` feature,
+//! and one of the GL backends of sokol_gfx. On platforms defaulting to another backend,
+//! select it explicitly:
+//!
+//! ~~~
+//! > cargo run -p clear-sdl2 --features sdl2,sokol/glcore33
+//! ~~~
+
+extern crate sdl2;
+extern crate sokol;
+
+use sdl2::event::Event;
+use sdl2::video::GLProfile;
+use sokol::gfx::*;
+
+fn main() {
+    let sdl = sdl2::init().unwrap();
+    let video = sdl.video().unwrap();
+
+    let gl_attr = video.gl_attr();
+    gl_attr.set_context_profile(GLProfile::Core);
+    gl_attr.set_context_version(3, 3);
+    gl_attr.set_depth_size(24);
+    gl_attr.set_stencil_size(8);
+
+    let window = video.window("clear-sdl2.rs", 400, 300)
+        .opengl()
+        .resizable()
+        .build()
+        .unwrap();
+
+    // sokol_gfx renders to the GL context which is current during sg_setup()
+    let _gl_context = window.gl_create_context().unwrap();
+    let _ = video.gl_set_swap_interval(1);
+
+    sg_setup(&SgDesc {
+        context: Some(SgContextDesc {
+            color_format: SgPixelFormat::RGBA8,
+            depth_format: SgPixelFormat::DepthStencil,
+            sample_count: 1,
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let mut pass_action = SgPassAction {
+        colors: vec!(
+            SgColorAttachmentAction {
+                action: SgAction::Clear,
+                val: [1.0, 0.0, 0.0, 1.0],
+            }
+        ),
+        ..Default::default()
+    };
+
+    let mut event_pump = sdl.event_pump().unwrap();
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            if let Event::Quit { .. } = event {
+                break 'running;
+            }
+        }
+
+        let g = pass_action.colors[0].val[1] + 0.01;
+        pass_action.colors[0].val[1] = if g > 1.0 { 0.0 } else { g };
+
+        let (width, height) = window.drawable_size();
+        sg_begin_default_pass(&pass_action, width as i32, height as i32);
+        sg_end_pass();
+        sg_commit();
+
+        window.gl_swap_window();
+    }
+
+    sg_shutdown();
+}
@@ -1,14 +1,28 @@
+extern crate bytemuck;
 extern crate nalgebra_glm as glm;
 extern crate sokol;
 
 use std::f32::consts::PI;
-use std::mem;
 
+use bytemuck::{Pod, Zeroable};
 use sokol::app::*;
 use sokol::gfx::*;
 
 const SAMPLE_COUNT: i32 = 4;
 
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable, Vertex)]
+struct Vertex {
+    pos: [f32; 3],
+    color: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, UniformBlock)]
+struct VsParams {
+    mvp: [[f32; 4]; 4],
+}
+
 #[derive(Default)]
 struct Cube {
     pipeline: SgPipeline,
@@ -23,39 +37,39 @@ impl SApp for Cube {
             ..Default::default()
         });
 
-        let vertices: [f32; 168] = [
-            -1.0, -1.0, -1.0, 1.0, 0.0, 0.0, 1.0,
-            1.0, -1.0, -1.0, 1.0, 0.0, 0.0, 1.0,
-            1.0, 1.0, -1.0, 1.0, 0.0, 0.0, 1.0,
-            -1.0, 1.0, -1.0, 1.0, 0.0, 0.0, 1.0,
-            -1.0, -1.0, 1.0, 0.0, 1.0, 0.0, 1.0,
-            1.0, -1.0, 1.0, 0.0, 1.0, 0.0, 1.0,
-            1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0,
-            -1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0,
-            -1.0, -1.0, -1.0, 0.0, 0.0, 1.0, 1.0,
-            -1.0, 1.0, -1.0, 0.0, 0.0, 1.0, 1.0,
-            -1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0,
-            -1.0, -1.0, 1.0, 0.0, 0.0, 1.0, 1.0,
-            1.0, -1.0, -1.0, 1.0, 0.5, 0.0, 1.0,
-            1.0, 1.0, -1.0, 1.0, 0.5, 0.0, 1.0,
-            1.0, 1.0, 1.0, 1.0, 0.5, 0.0, 1.0,
-            1.0, -1.0, 1.0, 1.0, 0.5, 0.0, 1.0,
-            -1.0, -1.0, -1.0, 0.0, 0.5, 1.0, 1.0,
-            -1.0, -1.0, 1.0, 0.0, 0.5, 1.0, 1.0,
-            1.0, -1.0, 1.0, 0.0, 0.5, 1.0, 1.0,
-            1.0, -1.0, -1.0, 0.0, 0.5, 1.0, 1.0,
-            -1.0, 1.0, -1.0, 1.0, 0.0, 0.5, 1.0,
-            -1.0, 1.0, 1.0, 1.0, 0.0, 0.5, 1.0,
-            1.0, 1.0, 1.0, 1.0, 0.0, 0.5, 1.0,
-            1.0, 1.0, -1.0, 1.0, 0.0, 0.5, 1.0
+        let vertices: [Vertex; 24] = [
+            Vertex { pos: [-1.0, -1.0, -1.0], color: [1.0, 0.0, 0.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, -1.0], color: [1.0, 0.0, 0.0, 1.0] },
+            Vertex { pos: [1.0, 1.0, -1.0], color: [1.0, 0.0, 0.0, 1.0] },
+            Vertex { pos: [-1.0, 1.0, -1.0], color: [1.0, 0.0, 0.0, 1.0] },
+            Vertex { pos: [-1.0, -1.0, 1.0], color: [0.0, 1.0, 0.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, 1.0], color: [0.0, 1.0, 0.0, 1.0] },
+            Vertex { pos: [1.0, 1.0, 1.0], color: [0.0, 1.0, 0.0, 1.0] },
+            Vertex { pos: [-1.0, 1.0, 1.0], color: [0.0, 1.0, 0.0, 1.0] },
+            Vertex { pos: [-1.0, -1.0, -1.0], color: [0.0, 0.0, 1.0, 1.0] },
+            Vertex { pos: [-1.0, 1.0, -1.0], color: [0.0, 0.0, 1.0, 1.0] },
+            Vertex { pos: [-1.0, 1.0, 1.0], color: [0.0, 0.0, 1.0, 1.0] },
+            Vertex { pos: [-1.0, -1.0, 1.0], color: [0.0, 0.0, 1.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, -1.0], color: [1.0, 0.5, 0.0, 1.0] },
+            Vertex { pos: [1.0, 1.0, -1.0], color: [1.0, 0.5, 0.0, 1.0] },
+            Vertex { pos: [1.0, 1.0, 1.0], color: [1.0, 0.5, 0.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, 1.0], color: [1.0, 0.5, 0.0, 1.0] },
+            Vertex { pos: [-1.0, -1.0, -1.0], color: [0.0, 0.5, 1.0, 1.0] },
+            Vertex { pos: [-1.0, -1.0, 1.0], color: [0.0, 0.5, 1.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, 1.0], color: [0.0, 0.5, 1.0, 1.0] },
+            Vertex { pos: [1.0, -1.0, -1.0], color: [0.0, 0.5, 1.0, 1.0] },
+            Vertex { pos: [-1.0, 1.0, -1.0], color: [1.0, 0.0, 0.5, 1.0] },
+            Vertex { pos: [-1.0, 1.0, 1.0], color: [1.0, 0.0, 0.5, 1.0] },
+            Vertex { pos: [1.0, 1.0, 1.0], color: [1.0, 0.0, 0.5, 1.0] },
+            Vertex { pos: [1.0, 1.0, -1.0], color: [1.0, 0.0, 0.5, 1.0] },
         ];
 
-        let vbuf = sg_make_buffer(
-            Some(&vertices),
+        let vbuf = sg_make_buffer_slice(
+            &vertices,
             &SgBufferDesc {
-                size: mem::size_of_val(&vertices),
-                buffer_type: SgBufferType::VertexBuffer,
                 usage: SgUsage::Immutable,
+                label: Some("cube-vertices"),
+                ..Default::default()
             },
         );
 
@@ -68,11 +82,10 @@ impl SApp for Cube {
             22, 21, 20, 23, 22, 20
         ];
 
-        let ibuf = sg_make_buffer(
-            Some(&indices),
+        let ibuf = sg_make_index_buffer(
+            &indices,
             &SgBufferDesc {
-                size: mem::size_of_val(&indices),
-                buffer_type: SgBufferType::IndexBuffer,
+                label: Some("cube-indices"),
                 ..Default::default()
             },
         );
@@ -163,16 +176,7 @@ impl SApp for Cube {
                 vs: SgShaderStageDesc {
                     source: Some(vs_src),
                     uniform_blocks: vec!(
-                        SgShaderUniformBlockDesc {
-                            size: 64,
-                            uniforms: vec!(
-                                SgShaderUniformDesc {
-                                    name: "mvp",
-                                    uniform_type: SgUniformType::Mat4,
-                                    ..Default::default()
-                                }
-                            ),
-                        }
+                        VsParams::uniform_block_desc()
                     ),
                     ..Default::default()
                 },
@@ -180,29 +184,13 @@ impl SApp for Cube {
                     source: Some(fs_src),
                     ..Default::default()
                 },
+                label: Some("cube-shader"),
             },
         );
 
         self.pipeline = sg_make_pipeline(
             &SgPipelineDesc {
-                layout: SgLayoutDesc {
-                    buffers: vec!(
-                        SgBufferLayoutDesc {
-                            stride: 28,
-                            ..Default::default()
-                        }
-                    ),
-                    attrs: vec!(
-                        SgVertexAttrDesc {
-                            format: SgVertexFormat::Float3,
-                            ..Default::default()
-                        },
-                        SgVertexAttrDesc {
-                            format: SgVertexFormat::Float4,
-                            ..Default::default()
-                        },
-                    ),
-                },
+                layout: Vertex::layout(),
                 shader: shd,
                 index_type: SgIndexType::UInt16,
                 depth: SgDepthState {
@@ -251,18 +239,18 @@ impl SApp for Cube {
         let rym = glm::rotation(self.ry * PI / 180.0, &glm::vec3(0.0, 1.0, 0.0));
         let model = rxm * rym;
 
-        let mvp: [[f32; 4]; 4] = (view_proj * model).into();
+        let vs_params = VsParams {
+            mvp: (view_proj * model).into(),
+        };
+
+        {
+            let mut pass = SgPassEncoder::begin_default(&pass_action, sapp_width(), sapp_height());
 
-        sg_begin_default_pass(&pass_action, sapp_width(), sapp_height());
-        sg_apply_pipeline(self.pipeline);
-        sg_apply_bindings(&self.bindings);
-        sg_apply_uniforms(
-            SgShaderStage::Vertex,
-            0,
-            &mvp,
-            64);
-        sg_draw(0, 36, 1);
-        sg_end_pass();
+            let mut pip = pass.apply_pipeline(self.pipeline);
+            pip.apply_bindings(&self.bindings);
+            pip.apply_uniform_block(SgShaderStage::Vertex, 0, &vs_params);
+            pip.draw(0, 36, 1);
+        }
         sg_commit();
     }
 
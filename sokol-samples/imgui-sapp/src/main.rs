@@ -35,7 +35,12 @@ impl SApp for ImGuiDemo {
     fn sapp_frame(&mut self) {
         let laptime = stm_laptime(&mut self.frame_time);
 
-        simgui_new_frame(sapp_width(), sapp_height(), stm_sec(laptime));
+        simgui_new_frame(&SImGuiFrameDesc {
+            width: sapp_width(),
+            height: sapp_height(),
+            delta_time: stm_sec(laptime),
+            dpi_scale: sapp_dpi_scale(),
+        });
 
         if imgui_begin_main_menu_bar() {
             if imgui_begin_menu(cstr!("demo"), true) {
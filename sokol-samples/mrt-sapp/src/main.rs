@@ -10,8 +10,8 @@ use sokol::gfx::*;
 const MSAA_SAMPLES: i32 = 4;
 
 struct MRT {
-    offscreen_pass_desc: SgPassDesc,
-    offscreen_pass: SgPass,
+    offscreen_images: Vec<SgOwnedImage>,
+    offscreen_pass: SgOwnedPass,
     offscreen_pipeline: SgPipeline,
     offscreen_bindings: SgBindings,
     fsq_pipeline: SgPipeline,
@@ -26,12 +26,6 @@ struct MRT {
 
 impl MRT {
     fn create_offscreen_pass(&mut self, width: i32, height: i32) {
-        sg_destroy_pass(self.offscreen_pass);
-        for att in &self.offscreen_pass_desc.color_attachments {
-            sg_destroy_image(att.image);
-        }
-        sg_destroy_image(self.offscreen_pass_desc.depth_stencil_attachment.image);
-
         let offscreen_sample_count = if sg_query_features().MSAARenderTargets {
             MSAA_SAMPLES
         } else {
@@ -53,31 +47,41 @@ impl MRT {
             pixel_format: SgPixelFormat::Depth,
             ..color_img_desc
         };
-        self.offscreen_pass_desc = SgPassDesc {
+        let images = vec![
+            SgOwned::new(sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc)),
+            SgOwned::new(sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc)),
+            SgOwned::new(sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc)),
+            SgOwned::new(sg_make_image(SG_IMAGE_CONTENT_NONE, &depth_img_desc)),
+        ];
+        let pass = SgOwned::new(sg_make_pass(&SgPassDesc {
             color_attachments: vec![
                 SgPassAttachmentDesc {
-                    image: sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc),
+                    image: *images[0],
                     ..Default::default()
                 },
                 SgPassAttachmentDesc {
-                    image: sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc),
+                    image: *images[1],
                     ..Default::default()
                 },
                 SgPassAttachmentDesc {
-                    image: sg_make_image(SG_IMAGE_CONTENT_NONE, &color_img_desc),
+                    image: *images[2],
                     ..Default::default()
                 },
             ],
             depth_stencil_attachment: SgPassAttachmentDesc {
-                image: sg_make_image(SG_IMAGE_CONTENT_NONE, &depth_img_desc),
+                image: *images[3],
                 ..Default::default()
             },
-        };
-        self.offscreen_pass = sg_make_pass(&self.offscreen_pass_desc);
+            label: Some("offscreen-pass"),
+        }));
+
+        // replacing the previous pass and images destroys them
+        self.offscreen_pass = pass;
+        self.offscreen_images = images;
 
         self.fsq_bindings.fs_images.clear();
-        for att in &self.offscreen_pass_desc.color_attachments {
-            self.fsq_bindings.fs_images.push(att.image);
+        for img in &self.offscreen_images[0..3] {
+            self.fsq_bindings.fs_images.push(**img);
         }
     }
 }
@@ -260,6 +264,7 @@ impl SApp for MRT {
                                     ..Default::default()
                                 }
                             ),
+                            ..Default::default()
                         }
                     ),
                     ..Default::default()
@@ -268,6 +273,7 @@ impl SApp for MRT {
                     source: Some(cube_fs_src),
                     ..Default::default()
                 },
+                label: Some("offscreen-shader"),
             },
         );
 
@@ -464,6 +470,7 @@ impl SApp for MRT {
                                     ..Default::default()
                                 }
                             ),
+                            ..Default::default()
                         }
                     ),
                     ..Default::default()
@@ -486,6 +493,7 @@ impl SApp for MRT {
                     ],
                     ..Default::default()
                 },
+                label: Some("fsq-shader"),
             },
         );
 
@@ -509,9 +517,9 @@ impl SApp for MRT {
         self.fsq_bindings = SgBindings {
             vertex_buffers: vec!(quad_vbuf),
             fs_images: vec![
-                self.offscreen_pass_desc.color_attachments[0].image,
-                self.offscreen_pass_desc.color_attachments[1].image,
-                self.offscreen_pass_desc.color_attachments[2].image,
+                *self.offscreen_images[0],
+                *self.offscreen_images[1],
+                *self.offscreen_images[2],
             ],
             ..Default::default()
         };
@@ -611,6 +619,7 @@ impl SApp for MRT {
                     ],
                     ..Default::default()
                 },
+                label: Some("dbg-shader"),
             }),
             ..Default::default()
         });
@@ -641,7 +650,7 @@ impl SApp for MRT {
 
         let mvp: [[f32; 4]; 4] = (view_proj * model).into();
 
-        sg_begin_pass(self.offscreen_pass, &self.offscreen_pass_action);
+        sg_begin_pass(*self.offscreen_pass, &self.offscreen_pass_action);
         sg_apply_pipeline(self.offscreen_pipeline);
         sg_apply_bindings(&self.offscreen_bindings);
         sg_apply_uniforms(SgShaderStage::Vertex, 0, &mvp, 64);
@@ -661,7 +670,7 @@ impl SApp for MRT {
         sg_apply_pipeline(self.dbg_pipeline);
         for i in 0..3 {
             sg_apply_viewport(i * 100, 0, 100, 100, false);
-            self.dbg_bindings.fs_images = vec![self.offscreen_pass_desc.color_attachments[i as usize].image];
+            self.dbg_bindings.fs_images = vec![*self.offscreen_images[i as usize]];
             sg_apply_bindings(&self.dbg_bindings);
             sg_draw(0, 4, 1);
         }
@@ -683,7 +692,7 @@ impl SApp for MRT {
 
 fn main() {
     let mrt_app = MRT {
-        offscreen_pass_desc: Default::default(),
+        offscreen_images: Vec::new(),
         offscreen_pass: Default::default(),
         offscreen_pipeline: Default::default(),
         offscreen_bindings: Default::default(),
@@ -0,0 +1,516 @@
+//! Generates the Rust module of a shader file from its reflection info.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::reflection::*;
+use crate::Error;
+
+const GFX: &str = "::sokol::gfx";
+
+pub fn generate(input: &Path, reflection: &Reflection, base_dir: &Path) -> Result<String, Error> {
+    let mut out = String::new();
+
+    writeln!(out, "// Generated by sokol-shdc from `{}`, do not edit.", input.display()).unwrap();
+
+    write_constants(&mut out, reflection);
+
+    for ub in collect_uniform_blocks(reflection).values() {
+        write_uniform_block(&mut out, ub)?;
+    }
+
+    let mut programs: BTreeMap<&str, Vec<(&Shader, &Program)>> = BTreeMap::new();
+
+    for shader in &reflection.shaders {
+        for program in &shader.programs {
+            programs.entry(&program.name).or_default().push((shader, program));
+        }
+    }
+
+    for (name, variants) in &programs {
+        write_shader_desc_fn(&mut out, name, variants, base_dir)?;
+    }
+
+    Ok(out)
+}
+
+fn write_constants(out: &mut String, reflection: &Reflection) {
+    let mut attrs = BTreeMap::new();
+    let mut slots = BTreeMap::new();
+
+    for shader in &reflection.shaders {
+        for program in &shader.programs {
+            for attr in &program.vs.inputs {
+                let name = format!("ATTR_{}_{}", upper_snake_case(&program.name), upper_snake_case(&attr.name));
+                attrs.entry(name).or_insert(attr.slot);
+            }
+
+            for stage in &[&program.vs, &program.fs] {
+                for ub in &stage.uniform_blocks {
+                    slots.entry(format!("SLOT_{}", upper_snake_case(&ub.struct_name))).or_insert(ub.slot);
+                }
+                for img in &stage.images {
+                    slots.entry(format!("SLOT_{}", upper_snake_case(&img.name))).or_insert(img.slot);
+                }
+            }
+        }
+    }
+
+    writeln!(out).unwrap();
+
+    for (name, slot) in attrs.iter().chain(slots.iter()) {
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "pub const {}: i32 = {};", name, slot).unwrap();
+    }
+}
+
+fn collect_uniform_blocks(reflection: &Reflection) -> BTreeMap<String, UniformBlock> {
+    let mut blocks = BTreeMap::new();
+
+    for shader in &reflection.shaders {
+        for program in &shader.programs {
+            for stage in &[&program.vs, &program.fs] {
+                for ub in &stage.uniform_blocks {
+                    // some shader languages don't report the block members
+                    let entry = blocks.entry(ub.struct_name.clone()).or_insert_with(|| ub.clone());
+                    if entry.uniforms.is_empty() {
+                        entry.uniforms = ub.uniforms.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Writes a `#[repr(C)]` struct matching the std140 layout of a uniform block, with
+/// explicit padding, and implements `SgUniformBlock` for it.
+fn write_uniform_block(out: &mut String, ub: &UniformBlock) -> Result<(), Error> {
+    let name = camel_case(&ub.struct_name);
+
+    writeln!(out).unwrap();
+    writeln!(out, "#[repr(C)]").unwrap();
+    writeln!(out, "#[derive(Copy, Clone, Debug)]").unwrap();
+    writeln!(out, "#[allow(dead_code)]").unwrap();
+    writeln!(out, "pub struct {} {{", name).unwrap();
+
+    let mut uniforms = ub.uniforms.clone();
+    uniforms.sort_by_key(|u| u.offset);
+
+    let mut offset = 0;
+
+    for u in &uniforms {
+        let (rust_type, uniform_type) = uniform_types(&u.uniform_type)?;
+
+        if u.offset < offset {
+            return Err(Error::Reflection(format!("overlapping uniform `{}` in block `{}`", u.name, ub.struct_name)));
+        }
+        if u.offset > offset {
+            writeln!(out, "    pub _pad_{}: [u8; {}],", offset, u.offset - offset).unwrap();
+        }
+
+        if u.array_count > 1 {
+            writeln!(out, "    pub {}: [{}; {}],", field_name(&u.name), rust_type, u.array_count).unwrap();
+        } else {
+            writeln!(out, "    pub {}: {},", field_name(&u.name), rust_type).unwrap();
+        }
+
+        offset = u.offset + uniform_size(uniform_type) * u.array_count.max(1);
+    }
+
+    if ub.size > offset {
+        writeln!(out, "    pub _pad_{}: [u8; {}],", offset, ub.size - offset).unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "const _: () = assert!(::std::mem::size_of::<{}>() == {});", name, ub.size).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "impl {}::SgUniformBlock for {} {{", GFX, name).unwrap();
+    writeln!(out, "    const LAYOUT: {0}::SgUniformLayout = {0}::SgUniformLayout::Std140;", GFX).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    fn uniforms() -> ::std::vec::Vec<{}::SgShaderUniformDesc<'static>> {{", GFX).unwrap();
+    writeln!(out, "        vec![").unwrap();
+
+    for u in &uniforms {
+        let (_, uniform_type) = uniform_types(&u.uniform_type)?;
+        write_uniform_desc(out, "            ", &u.name, uniform_type, u.array_count.max(1));
+    }
+
+    writeln!(out, "        ]").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    Ok(())
+}
+
+fn write_shader_desc_fn(out: &mut String,
+                        name: &str,
+                        variants: &[(&Shader, &Program)],
+                        base_dir: &Path) -> Result<(), Error> {
+    writeln!(out).unwrap();
+    writeln!(out, "/// Returns the description of the `{}` program for `backend`, or `None` if it", name).unwrap();
+    writeln!(out, "/// wasn't compiled for the backend's shader language.").unwrap();
+    writeln!(out, "#[allow(dead_code)]").unwrap();
+    writeln!(out, "pub fn {}_shader_desc(backend: {}::SgBackend) -> Option<{}::SgShaderDesc<'static>> {{",
+             snake_case(name), GFX, GFX).unwrap();
+    writeln!(out, "    match backend {{").unwrap();
+
+    for (shader, program) in variants {
+        let backends = slang_backends(&shader.slang)
+            .ok_or_else(|| Error::Reflection(format!("unsupported shader language `{}`", shader.slang)))?;
+
+        let patterns: Vec<String> = backends.iter()
+            .map(|backend| format!("{}::SgBackend::{}", GFX, backend))
+            .collect();
+
+        let is_gl = shader.slang.starts_with("glsl");
+
+        writeln!(out, "        {} => Some({}::SgShaderDesc {{", patterns.join(" | "), GFX).unwrap();
+
+        let mut attrs: Vec<&Attr> = program.vs.inputs.iter().collect();
+        attrs.sort_by_key(|attr| attr.slot);
+
+        write_vec_start(out, "            attrs", attrs.is_empty());
+        for (slot, attr) in fill_slots(&attrs, |attr| attr.slot) {
+            match attr {
+                Some(attr) => writeln!(out, "                {}::SgShaderAttrDesc {{ name: {:?}, sem_name: {:?}, sem_index: {} }},",
+                                       GFX, attr.name, attr.sem_name, attr.sem_index).unwrap(),
+                None => writeln!(out, "                {}::SgShaderAttrDesc::default(), // slot {}", GFX, slot).unwrap(),
+            }
+        }
+        write_vec_end(out, "            ", attrs.is_empty());
+
+        write_stage_desc(out, "vs", &program.vs, is_gl, base_dir)?;
+        write_stage_desc(out, "fs", &program.fs, is_gl, base_dir)?;
+
+        writeln!(out, "            label: Some({:?}),", format!("{}_shader", name)).unwrap();
+        writeln!(out, "        }}),").unwrap();
+    }
+
+    writeln!(out, "        _ => None,").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    Ok(())
+}
+
+fn write_stage_desc(out: &mut String,
+                    stage_name: &str,
+                    stage: &Stage,
+                    is_gl: bool,
+                    base_dir: &Path) -> Result<(), Error> {
+    let path = resolve_path(&stage.path, base_dir)?;
+    let path = path.to_str()
+        .ok_or_else(|| Error::Reflection(format!("invalid path `{}`", path.display())))?;
+
+    writeln!(out, "            {}: {}::SgShaderStageDesc {{", stage_name, GFX).unwrap();
+
+    if stage.is_binary {
+        writeln!(out, "                source: None,").unwrap();
+        writeln!(out, "                byte_code: Some(include_bytes!({:?})),", path).unwrap();
+    } else {
+        writeln!(out, "                source: Some(include_str!({:?})),", path).unwrap();
+        writeln!(out, "                byte_code: None,").unwrap();
+    }
+
+    writeln!(out, "                entry: Some({:?}),", stage.entry_point).unwrap();
+
+    let mut uniform_blocks: Vec<&UniformBlock> = stage.uniform_blocks.iter().collect();
+    uniform_blocks.sort_by_key(|ub| ub.slot);
+
+    write_vec_start(out, "                uniform_blocks", uniform_blocks.is_empty());
+    for (slot, ub) in fill_slots(&uniform_blocks, |ub| ub.slot) {
+        let ub = match ub {
+            Some(ub) => ub,
+            None => {
+                writeln!(out, "                    {}::SgShaderUniformBlockDesc::default(), // slot {}", GFX, slot).unwrap();
+                continue;
+            }
+        };
+
+        writeln!(out, "                    {}::SgShaderUniformBlockDesc {{", GFX).unwrap();
+        writeln!(out, "                        size: {},", ub.size).unwrap();
+        writeln!(out, "                        layout: {}::SgUniformLayout::Std140,", GFX).unwrap();
+
+        if is_gl {
+            // sokol-shdc flattens GLSL uniform blocks into a single vec4 or ivec4 array
+            let is_int = !ub.uniforms.is_empty() && ub.uniforms.iter()
+                .all(|u| matches!(uniform_types(&u.uniform_type), Ok((_, t)) if t.starts_with("Int")));
+            let uniform_type = if is_int { "Int4" } else { "Float4" };
+
+            writeln!(out, "                        uniforms: vec![").unwrap();
+            write_uniform_desc(out, "                            ", &ub.struct_name, uniform_type, ub.size.div_ceil(16));
+            writeln!(out, "                        ],").unwrap();
+        } else {
+            writeln!(out, "                        uniforms: vec![],").unwrap();
+        }
+
+        writeln!(out, "                    }},").unwrap();
+    }
+    write_vec_end(out, "                ", uniform_blocks.is_empty());
+
+    let mut images: Vec<&Image> = stage.images.iter().collect();
+    images.sort_by_key(|img| img.slot);
+
+    write_vec_start(out, "                images", images.is_empty());
+    for (slot, img) in fill_slots(&images, |img| img.slot) {
+        match img {
+            Some(img) => writeln!(out, "                    {}::SgShaderImageDesc {{ name: {:?}, image_type: {}::SgImageType::{} }},",
+                                  GFX, img.name, GFX, image_type(&img.image_type)?).unwrap(),
+            None => writeln!(out, "                    {}::SgShaderImageDesc::default(), // slot {}", GFX, slot).unwrap(),
+        }
+    }
+    write_vec_end(out, "                ", images.is_empty());
+
+    writeln!(out, "            }},").unwrap();
+
+    Ok(())
+}
+
+fn write_vec_start(out: &mut String, field: &str, is_empty: bool) {
+    if is_empty {
+        writeln!(out, "{}: vec![],", field).unwrap();
+    } else {
+        writeln!(out, "{}: vec![", field).unwrap();
+    }
+}
+
+fn write_vec_end(out: &mut String, indent: &str, is_empty: bool) {
+    if !is_empty {
+        writeln!(out, "{}],", indent).unwrap();
+    }
+}
+
+fn write_uniform_desc(out: &mut String, indent: &str, name: &str, uniform_type: &str, array_count: usize) {
+    writeln!(out, "{}{}::SgShaderUniformDesc {{ name: {:?}, uniform_type: {}::SgUniformType::{}, array_count: {} }},",
+             indent, GFX, name, GFX, uniform_type, array_count).unwrap();
+}
+
+/// Pairs each slot up to the highest one used with its item, if any.
+fn fill_slots<'a, T>(items: &[&'a T], slot: impl Fn(&T) -> i32) -> Vec<(i32, Option<&'a T>)> {
+    let num_slots = items.iter().map(|item| slot(item) + 1).max().unwrap_or(0);
+
+    (0..num_slots)
+        .map(|index| (index, items.iter().find(|item| slot(item) == index).copied()))
+        .collect()
+}
+
+fn resolve_path(path: &str, base_dir: &Path) -> Result<PathBuf, Error> {
+    let candidates = [base_dir.join(path), PathBuf::from(path)];
+
+    candidates.iter()
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+        .ok_or_else(|| Error::Reflection(format!("shader file `{}` not found", path)))
+}
+
+fn slang_backends(slang: &str) -> Option<&'static [&'static str]> {
+    let backends: &'static [&'static str] = match slang {
+        "glsl330" => &["GLCORE33"],
+        "glsl100" => &["GLES2"],
+        "glsl300es" => &["GLES3"],
+        "hlsl4" | "hlsl5" => &["D3D11"],
+        "metal_macos" => &["MetalMacOS"],
+        "metal_ios" => &["MetalIOS"],
+        "metal_sim" => &["MetalSimulator"],
+        "wgsl" => &["WGPU"],
+        _ => return None,
+    };
+
+    Some(backends)
+}
+
+/// Returns the Rust type and the `SgUniformType` variant of a uniform.
+fn uniform_types(uniform_type: &str) -> Result<(&'static str, &'static str), Error> {
+    let types = match uniform_type.to_lowercase().as_str() {
+        "float" => ("f32", "Float"),
+        "float2" | "vec2" => ("[f32; 2]", "Float2"),
+        "float3" | "vec3" => ("[f32; 3]", "Float3"),
+        "float4" | "vec4" => ("[f32; 4]", "Float4"),
+        "int" => ("i32", "Int"),
+        "int2" | "ivec2" => ("[i32; 2]", "Int2"),
+        "int3" | "ivec3" => ("[i32; 3]", "Int3"),
+        "int4" | "ivec4" => ("[i32; 4]", "Int4"),
+        "mat4" => ("[[f32; 4]; 4]", "Mat4"),
+        _ => return Err(Error::Reflection(format!("unsupported uniform type `{}`", uniform_type))),
+    };
+
+    Ok(types)
+}
+
+fn uniform_size(uniform_type: &str) -> usize {
+    match uniform_type {
+        "Float" | "Int" => 4,
+        "Float2" | "Int2" => 8,
+        "Float3" | "Int3" => 12,
+        "Float4" | "Int4" => 16,
+        _ => 64,
+    }
+}
+
+fn image_type(image_type: &str) -> Result<&'static str, Error> {
+    let image_type = match image_type.to_lowercase().trim_start_matches('_') {
+        "2d" => "Texture2D",
+        "cube" => "TextureCube",
+        "3d" => "Texture3D",
+        "array" => "TextureArray",
+        _ => return Err(Error::Reflection(format!("unsupported image type `{}`", image_type))),
+    };
+
+    Ok(image_type)
+}
+
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if (c == '_' || (c.is_uppercase() && prev_lower)) && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c != '_' {
+            word.push(c);
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn upper_snake_case(name: &str) -> String {
+    words(name).join("_").to_uppercase()
+}
+
+fn snake_case(name: &str) -> String {
+    words(name).join("_").to_lowercase()
+}
+
+fn camel_case(name: &str) -> String {
+    words(name).iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn field_name(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+        "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+        "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+        "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro", "override",
+        "priv", "typeof", "unsized", "virtual", "yield", "try",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_fixture(name: &str) -> String {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        let input = dir.join(format!("{}.glsl", name));
+        let yaml = std::fs::read_to_string(dir.join(format!("{}.yaml", name))).unwrap();
+        let reflection: Reflection = serde_yaml::from_str(&yaml).unwrap();
+
+        generate(&input, &reflection, &dir).unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        let module = generate_fixture("quad");
+
+        assert!(module.contains("pub const ATTR_QUAD_POSITION: i32 = 0;"));
+        assert!(module.contains("pub const ATTR_QUAD_TEXCOORD0: i32 = 2;"));
+        assert!(module.contains("pub const SLOT_VS_PARAMS: i32 = 0;"));
+        assert!(module.contains("pub const SLOT_TEX: i32 = 0;"));
+    }
+
+    #[test]
+    fn uniform_block() {
+        let module = generate_fixture("quad");
+
+        assert_eq!(module.matches("pub struct VsParams {").count(), 1);
+        assert!(module.contains(concat!(
+            "pub struct VsParams {\n",
+            "    pub mvp: [[f32; 4]; 4],\n",
+            "    pub scale: f32,\n",
+            "    pub _pad_68: [u8; 12],\n",
+            "}\n",
+        )));
+        assert!(module.contains("const _: () = assert!(::std::mem::size_of::<VsParams>() == 80);"));
+        assert!(module.contains("impl ::sokol::gfx::SgUniformBlock for VsParams {"));
+        assert!(module.contains(
+            "::sokol::gfx::SgShaderUniformDesc { name: \"scale\", uniform_type: ::sokol::gfx::SgUniformType::Float, array_count: 1 },"
+        ));
+    }
+
+    #[test]
+    fn shader_desc() {
+        let module = generate_fixture("quad");
+
+        assert_eq!(module.matches("pub fn quad_shader_desc(backend: ::sokol::gfx::SgBackend)").count(), 1);
+        assert!(module.contains("::sokol::gfx::SgBackend::GLCORE33 => Some(::sokol::gfx::SgShaderDesc {"));
+        assert!(module.contains("::sokol::gfx::SgBackend::MetalMacOS => Some(::sokol::gfx::SgShaderDesc {"));
+        assert!(module.contains("_ => None,"));
+
+        // attribute slot 1 is unused
+        assert!(module.contains("::sokol::gfx::SgShaderAttrDesc::default(), // slot 1"));
+        assert!(module.contains(
+            "::sokol::gfx::SgShaderAttrDesc { name: \"texcoord0\", sem_name: \"TEXCOORD\", sem_index: 2 },"
+        ));
+
+        // GLSL uniform blocks are flattened to vec4 arrays
+        assert!(module.contains(
+            "::sokol::gfx::SgShaderUniformDesc { name: \"vs_params\", uniform_type: ::sokol::gfx::SgUniformType::Float4, array_count: 5 },"
+        ));
+        assert!(module.contains("uniforms: vec![],"));
+
+        assert!(module.contains(
+            "::sokol::gfx::SgShaderImageDesc { name: \"tex\", image_type: ::sokol::gfx::SgImageType::Texture2D },"
+        ));
+        assert!(module.contains("entry: Some(\"main0\"),"));
+        assert!(module.contains("label: Some(\"quad_shader\"),"));
+
+        for stage in &["glsl330_vs.glsl", "glsl330_fs.glsl", "metal_macos_vs.metal", "metal_macos_fs.metal"] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/quad")
+                .join(format!("quad_quad_{}", stage))
+                .canonicalize()
+                .unwrap();
+            assert!(module.contains(&format!("source: Some(include_str!({:?})),", path.to_str().unwrap())));
+        }
+    }
+
+    #[test]
+    fn unsupported_slang() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/quad");
+        let yaml = std::fs::read_to_string(dir.join("quad.yaml")).unwrap()
+            .replace("slang: metal_macos", "slang: spirv_vk");
+        let reflection: Reflection = serde_yaml::from_str(&yaml).unwrap();
+
+        match generate(&dir.join("quad.glsl"), &reflection, &dir) {
+            Err(Error::Reflection(message)) => assert!(message.contains("spirv_vk")),
+            other => panic!("expected a reflection error, got {:?}", other),
+        }
+    }
+}
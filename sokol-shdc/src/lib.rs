@@ -0,0 +1,233 @@
+/*!
+
+Build script helper which compiles shaders annotated for
+[sokol-shdc](https://github.com/floooh/sokol-tools/blob/master/docs/sokol-shdc.md)
+into Rust modules.
+
+For each input file, `sokol-shdc` cross-compiles the shader programs to the shader
+languages of the target platform. The generated module contains:
+
+- a `<program>_shader_desc(backend)` function per program, which returns the `SgShaderDesc`
+  for the current backend,
+- a `#[repr(C)]` struct per uniform block, implementing `SgUniformBlock` for use with
+  `sg_apply_uniform_block()`,
+- `ATTR_<PROGRAM>_<NAME>` constants for vertex attribute locations, and `SLOT_<NAME>`
+  constants for uniform block and image bind slots.
+
+The `sokol-shdc` executable is looked up in `PATH`, or can be set with the `SOKOL_SHDC`
+environment variable.
+
+# Example
+
+In `build.rs`:
+
+```no_run
+sokol_shdc::compile("src/shaders/cube.glsl");
+```
+
+In the application:
+
+```ignore
+mod cube_shader {
+    include!(concat!(env!("OUT_DIR"), "/cube.rs"));
+}
+
+let shd = sg_make_shader(&cube_shader::cube_shader_desc(sg_query_backend()).unwrap());
+```
+
+*/
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod codegen;
+mod reflection;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// `sokol-shdc` failed to compile the shader, with its output.
+    Compile(String),
+    /// The reflection info written by `sokol-shdc` couldn't be read or used.
+    Reflection(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Compile(output) => write!(f, "sokol-shdc failed:\n{}", output),
+            Error::Reflection(message) => write!(f, "invalid sokol-shdc output: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Compiles annotated shader files with `sokol-shdc`.
+#[derive(Clone, Debug)]
+pub struct Shdc {
+    exe: PathBuf,
+    slangs: Vec<String>,
+    defines: Vec<String>,
+    bytecode: bool,
+    out_dir: PathBuf,
+}
+
+impl Shdc {
+    /// Creates a compiler for the target platform of the running build script.
+    pub fn new() -> Self {
+        Shdc {
+            exe: env::var_os("SOKOL_SHDC").map_or_else(|| PathBuf::from("sokol-shdc"), PathBuf::from),
+            slangs: Vec::new(),
+            defines: Vec::new(),
+            bytecode: false,
+            out_dir: env::var_os("OUT_DIR").map(PathBuf::from).unwrap_or_default(),
+        }
+    }
+
+    /// Sets the path of the `sokol-shdc` executable.
+    pub fn exe<P: AsRef<Path>>(mut self, exe: P) -> Self {
+        self.exe = exe.as_ref().to_path_buf();
+        self
+    }
+
+    /// Adds a shader language, e.g. `glsl330` or `metal_macos`. If none are added, the
+    /// languages of the target platform are used, see `default_slangs()`.
+    pub fn slang(mut self, slang: &str) -> Self {
+        self.slangs.push(slang.to_string());
+        self
+    }
+
+    /// Adds a preprocessor define, for `@if` blocks in the shader.
+    pub fn define(mut self, define: &str) -> Self {
+        self.defines.push(define.to_string());
+        self
+    }
+
+    /// Compiles HLSL and Metal shaders to bytecode, which requires building on Windows
+    /// or MacOS respectively.
+    pub fn bytecode(mut self, bytecode: bool) -> Self {
+        self.bytecode = bytecode;
+        self
+    }
+
+    /// Sets the directory the Rust modules are written to, `OUT_DIR` by default.
+    pub fn out_dir<P: AsRef<Path>>(mut self, out_dir: P) -> Self {
+        self.out_dir = out_dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// Compiles a shader file, and writes its Rust module to `<out_dir>/<file stem>.rs`.
+    /// Returns the path of the module.
+    pub fn compile<P: AsRef<Path>>(&self, input: P) -> Result<PathBuf, Error> {
+        let input = input.as_ref();
+
+        println!("cargo:rerun-if-changed={}", input.display());
+        println!("cargo:rerun-if-env-changed=SOKOL_SHDC");
+
+        let stem = input.file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, "invalid shader file name")))?;
+
+        // keep the files of each shader apart, so the reflection info can be found by extension
+        let shdc_dir = self.out_dir.join("sokol-shdc").join(stem);
+        if shdc_dir.exists() {
+            fs::remove_dir_all(&shdc_dir)?;
+        }
+        fs::create_dir_all(&shdc_dir)?;
+
+        let slangs = if self.slangs.is_empty() { default_slangs() } else { self.slangs.clone() };
+
+        let mut cmd = Command::new(&self.exe);
+        cmd.arg("--input").arg(input)
+            .arg("--output").arg(shdc_dir.join(stem))
+            .arg("--slang").arg(slangs.join(":"))
+            .arg("--format").arg("bare_yaml");
+
+        if !self.defines.is_empty() {
+            cmd.arg("--defines").arg(self.defines.join(":"));
+        }
+        if self.bytecode {
+            cmd.arg("--bytecode");
+        }
+
+        let output = cmd.output().map_err(|err| {
+            Error::Io(io::Error::new(err.kind(), format!("failed to run {}: {}", self.exe.display(), err)))
+        })?;
+
+        if !output.status.success() {
+            let mut message = String::from_utf8_lossy(&output.stderr).into_owned();
+            message.push_str(&String::from_utf8_lossy(&output.stdout));
+            return Err(Error::Compile(message));
+        }
+
+        let yaml = find_reflection(&shdc_dir)?;
+        let reflection: reflection::Reflection = serde_yaml::from_str(&fs::read_to_string(&yaml)?)
+            .map_err(|err| Error::Reflection(format!("{}: {}", yaml.display(), err)))?;
+
+        let module = codegen::generate(input, &reflection, &shdc_dir)?;
+
+        let module_path = self.out_dir.join(format!("{}.rs", stem));
+        fs::write(&module_path, module)?;
+
+        Ok(module_path)
+    }
+}
+
+impl Default for Shdc {
+    fn default() -> Self {
+        Shdc::new()
+    }
+}
+
+/// Compiles a shader file for the target platform, see `Shdc::compile()`.
+///
+/// Panics on errors, so the build script fails with the compiler output.
+pub fn compile<P: AsRef<Path>>(input: P) -> PathBuf {
+    match Shdc::new().compile(input.as_ref()) {
+        Ok(path) => path,
+        Err(err) => panic!("failed to compile {}: {}", input.as_ref().display(), err),
+    }
+}
+
+/// Returns the shader languages for the backends sokol_gfx supports on the target
+/// platform of the running build script.
+pub fn default_slangs() -> Vec<String> {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
+    let slangs: &[&str] = match target_os.as_str() {
+        "windows" => &["hlsl5", "glsl330"],
+        "macos" => &["metal_macos", "glsl330"],
+        "ios" => &["metal_ios", "metal_sim", "glsl300es"],
+        "android" => &["glsl300es"],
+        "emscripten" => &["glsl300es", "wgsl"],
+        _ => &["glsl330", "glsl300es"],
+    };
+
+    slangs.iter().map(|slang| slang.to_string()).collect()
+}
+
+fn find_reflection(dir: &Path) -> Result<PathBuf, Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml") {
+            return Ok(path);
+        }
+    }
+
+    Err(Error::Reflection(format!("no reflection info found in {}", dir.display())))
+}
@@ -0,0 +1,70 @@
+//! The reflection info written by `sokol-shdc --format bare_yaml`.
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Reflection {
+    pub shaders: Vec<Shader>,
+}
+
+/// All programs of the input file, compiled for one shader language.
+#[derive(Debug, Deserialize)]
+pub struct Shader {
+    pub slang: String,
+    pub programs: Vec<Program>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Program {
+    pub name: String,
+    pub vs: Stage,
+    pub fs: Stage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Stage {
+    pub path: String,
+    #[serde(default)]
+    pub is_binary: bool,
+    pub entry_point: String,
+    #[serde(default)]
+    pub inputs: Vec<Attr>,
+    #[serde(default)]
+    pub uniform_blocks: Vec<UniformBlock>,
+    #[serde(default)]
+    pub images: Vec<Image>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Attr {
+    pub slot: i32,
+    pub name: String,
+    pub sem_name: String,
+    pub sem_index: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UniformBlock {
+    pub slot: i32,
+    pub size: usize,
+    pub struct_name: String,
+    #[serde(default)]
+    pub uniforms: Vec<Uniform>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Uniform {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub uniform_type: String,
+    pub array_count: usize,
+    pub offset: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Image {
+    pub slot: i32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub image_type: String,
+}
@@ -0,0 +1,11 @@
+#version 330
+
+uniform sampler2D tex;
+
+layout(location = 0) out vec4 frag_color;
+in vec2 uv;
+
+void main()
+{
+    frag_color = texture(tex, uv);
+}
@@ -0,0 +1,12 @@
+#version 330
+
+uniform vec4 vs_params[5];
+layout(location = 0) in vec4 position;
+out vec2 uv;
+layout(location = 2) in vec2 texcoord0;
+
+void main()
+{
+    gl_Position = mat4(vs_params[0], vs_params[1], vs_params[2], vs_params[3]) * (position * vs_params[4].x);
+    uv = texcoord0;
+}
@@ -0,0 +1,30 @@
+#include <metal_stdlib>
+#include <simd/simd.h>
+
+using namespace metal;
+
+struct vs_params
+{
+    float4x4 mvp;
+    float scale;
+};
+
+struct main0_out
+{
+    float2 uv [[user(locn0)]];
+    float4 gl_Position [[position]];
+};
+
+struct main0_in
+{
+    float4 position [[attribute(0)]];
+    float2 texcoord0 [[attribute(2)]];
+};
+
+vertex main0_out main0(main0_in in [[stage_in]], constant vs_params& _21 [[buffer(0)]])
+{
+    main0_out out = {};
+    out.gl_Position = _21.mvp * (in.position * _21.scale);
+    out.uv = in.texcoord0;
+    return out;
+}
@@ -11,29 +11,119 @@ fn build_new() -> (Build, Tool) {
     (build, tool)
 }
 
-fn select_sokol_gfx_renderer(build: &mut Build, is_msvc: bool, is_impl: bool) {
+const SOKOL_GFX_RENDERERS: [(&str, &str); 6] = [
+    ("glcore33", "SOKOL_GLCORE33"),
+    ("gles3", "SOKOL_GLES3"),
+    ("d3d11", "SOKOL_D3D11"),
+    ("metal", "SOKOL_METAL"),
+    ("wgpu", "SOKOL_WGPU"),
+    ("dummy", "SOKOL_DUMMY_BACKEND"),
+];
+
+fn sokol_gfx_renderer(is_msvc: bool) -> (&'static str, &'static str) {
+    //
+    // select sokol_gfx renderer from cargo features, if one is set
+    //
+    let selected: Vec<&(&str, &str)> = SOKOL_GFX_RENDERERS
+        .iter()
+        .filter(|(feature, _)| {
+            env::var(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_ok()
+        })
+        .collect();
+
+    if selected.len() > 1 {
+        let names: Vec<&str> = selected.iter().map(|(feature, _)| *feature).collect();
+        panic!("sokol-sys: only one sokol_gfx renderer feature can be enabled at a time, found: {}",
+               names.join(", "));
+    }
+
     //
-    // select sokol_gfx renderer, defaults to:
+    // no feature set, defaults to:
     // - Windows: D3D11 with MSVC, GLCORE33 otherwise
     // - MacOS: Metal
     // - Linux: GLCORE33
     //
-    if cfg!(target_os = "windows") && is_msvc {
-        build.flag("-DSOKOL_D3D11");
-    } else if cfg!(target_os = "macos") {
-        build.flag("-DSOKOL_METAL");
-    } else {
-        build.flag("-DSOKOL_GLCORE33");
+    match selected.first() {
+        Some(&&(feature, define)) => (feature, define),
+        None => {
+            if cfg!(target_os = "windows") && is_msvc {
+                ("d3d11", "SOKOL_D3D11")
+            } else if cfg!(target_os = "macos") {
+                ("metal", "SOKOL_METAL")
+            } else {
+                ("glcore33", "SOKOL_GLCORE33")
+            }
+        }
     }
+}
+
+fn select_sokol_gfx_renderer(build: &mut Build, is_msvc: bool, is_impl: bool) {
+    let (renderer, define) = sokol_gfx_renderer(is_msvc);
+
+    build.flag(format!("-D{}", define));
 
     if is_impl {
-        if cfg!(target_os = "windows") && is_msvc {
-            println!("cargo:rustc-cfg=gfx=\"d3d11\"");
-        } else if cfg!(target_os = "macos") {
-            println!("cargo:rustc-cfg=gfx=\"metal\"");
+        println!("cargo:rustc-cfg=gfx=\"{}\"", renderer);
+    }
+}
+
+//
+// sokol_app only supports some of the sokol_gfx renderers on each platform, so it is
+// compiled separately with the platform's 3D API:
+// - Windows: GLCORE33 if selected, or without MSVC, D3D11 otherwise
+// - MacOS: GLCORE33 if selected, Metal otherwise
+// - Linux: GLES3 if selected, GLCORE33 otherwise
+//
+fn sokol_app_renderer(renderer: &str, is_msvc: bool) -> &'static str {
+    if cfg!(target_os = "windows") {
+        if renderer == "glcore33" || !is_msvc {
+            "SOKOL_GLCORE33"
+        } else {
+            "SOKOL_D3D11"
+        }
+    } else if cfg!(target_os = "macos") {
+        if renderer == "glcore33" {
+            "SOKOL_GLCORE33"
         } else {
-            println!("cargo:rustc-cfg=gfx=\"glcore33\"");
+            "SOKOL_METAL"
         }
+    } else if renderer == "gles3" {
+        "SOKOL_GLES3"
+    } else {
+        "SOKOL_GLCORE33"
+    }
+}
+
+fn add_platform_flags(build: &mut Build, is_msvc: bool, is_debug: bool) {
+    //
+    // MacOS: need ARC, so compile .m files with -fobjc-arc
+    //
+    if cfg!(target_os = "macos") {
+        build
+            .flag("-fobjc-arc");
+    }
+
+    //
+    // silence some warnings
+    //
+    build
+        .flag_if_supported("-Wno-unused-parameter");
+
+    //
+    // x86_64-pc-windows-gnu: additional compile flags
+    //
+    if cfg!(target_os = "windows") && !is_msvc {
+        build
+            .flag("-D_WIN32_WINNT=0x0601")
+            .flag_if_supported("-Wno-cast-function-type")
+            .flag_if_supported("-Wno-sign-compare")
+            .flag_if_supported("-Wno-unknown-pragmas");
+    }
+
+    if is_debug {
+        build
+            .flag("-D_DEBUG")
+            .flag("-DSOKOL_DEBUG");
     }
 }
 
@@ -49,12 +139,8 @@ fn make_sokol() {
     build
         .include("external/sokol");
 
-    //
-    // MacOS: need ARC, so compile sokol.m with -fobjc-arc
-    //
     if cfg!(target_os = "macos") {
         build
-            .flag("-fobjc-arc")
             .file("src/sokol.m");
     } else {
         build
@@ -67,35 +153,37 @@ fn make_sokol() {
     select_sokol_gfx_renderer(&mut build, is_msvc, true);
 
     //
-    // silence some warnings
+    // the dummy renderer is meant for headless builds and tests, so failed validation
+    // checks only fail the resource instead of aborting the process
     //
+    let (renderer, _) = sokol_gfx_renderer(is_msvc);
+
+    if renderer == "dummy" {
+        build.define("SOKOL_VALIDATE_NON_FATAL", None);
+    }
+
+    add_platform_flags(&mut build, is_msvc, is_debug);
+
     build
-        .flag_if_supported("-Wno-unused-parameter");
+        .compile("sokol-sys");
 
     //
-    // x86_64-pc-windows-gnu: additional compile/link flags
+    // sokol_app and the platform libs are left out of dummy builds, and sokol_audio
+    // uses its dummy backend
     //
-    if cfg!(target_os = "windows") {
-        if !is_msvc {
-            build
-                .flag("-D_WIN32_WINNT=0x0601")
-                .flag_if_supported("-Wno-cast-function-type")
-                .flag_if_supported("-Wno-sign-compare")
-                .flag_if_supported("-Wno-unknown-pragmas");
-
-            println!("cargo:rustc-link-lib=static=gdi32");
-            println!("cargo:rustc-link-lib=static=ole32");
-        }
+    if renderer == "dummy" {
+        return;
     }
 
-    if is_debug {
-        build
-            .flag("-D_DEBUG")
-            .flag("-DSOKOL_DEBUG");
-    }
+    make_sokol_app(renderer, is_msvc, is_debug);
 
-    build
-        .compile("sokol-sys");
+    //
+    // Windows (not MSVC): libs
+    //
+    if cfg!(target_os = "windows") && !is_msvc {
+        println!("cargo:rustc-link-lib=static=gdi32");
+        println!("cargo:rustc-link-lib=static=ole32");
+    }
 
     //
     // MacOS: frameworks
@@ -118,6 +206,28 @@ fn make_sokol() {
     }
 }
 
+fn make_sokol_app(renderer: &str, is_msvc: bool, is_debug: bool) {
+    let (mut build, _) = build_new();
+
+    build
+        .include("external/sokol");
+
+    if cfg!(target_os = "macos") {
+        build
+            .file("src/sokol_app.m");
+    } else {
+        build
+            .file("src/sokol_app.c");
+    }
+
+    build.flag(format!("-D{}", sokol_app_renderer(renderer, is_msvc)));
+
+    add_platform_flags(&mut build, is_msvc, is_debug);
+
+    build
+        .compile("sokol-sys-app");
+}
+
 fn make_sokol_imgui() {
     let (mut build, tool) = build_new();
 
@@ -140,6 +250,7 @@ fn make_sokol_imgui() {
         "external/imgui/imgui.cpp",
         "external/imgui/imgui_demo.cpp",
         "external/imgui/imgui_draw.cpp",
+        "external/imgui/imgui_tables.cpp",
         "external/imgui/imgui_widgets.cpp"
     ]);
 
@@ -169,7 +280,51 @@ fn make_sokol_imgui() {
         .compile("sokol-sys-imgui");
 }
 
+#[cfg(feature = "bindgen")]
+fn make_sokol_bindings() {
+    extern crate bindgen;
+
+    use std::path::PathBuf;
+
+    let (_, tool) = build_new();
+    let (_, define) = sokol_gfx_renderer(tool.is_like_msvc());
+
+    //
+    // raw bindings, used to check the layout of the hand-written
+    // #[repr(C)] mirrors in the sokol and sokol-imgui crates
+    //
+    let bindings = bindgen::Builder::default()
+        .header("src/sokol.h")
+        .header("external/sokol/util/sokol_imgui.h")
+        .clang_arg("-Iexternal/sokol")
+        .clang_arg(format!("-D{}", define))
+        .allowlist_type("(sg|sapp|saudio|simgui)_.*")
+        .allowlist_var("_?(SG|SAPP|SAUDIO)_.*")
+        // log items are passed around as plain ids
+        .blocklist_type("(sg|sapp|saudio|simgui)_log_item(_t)?")
+        .rustified_enum(".*")
+        .prepend_enum_name(false)
+        .layout_tests(false)
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+        .generate()
+        .expect("sokol-sys: failed to generate bindings");
+
+    //
+    // the bindings are committed, so that the layout checks don't require libclang
+    //
+    let out_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("src/sokol_raw.rs"))
+        .expect("sokol-sys: failed to write bindings");
+}
+
 fn main() {
     make_sokol();
-    //make_sokol_imgui();
+
+    #[cfg(feature = "bindgen")]
+    make_sokol_bindings();
+
+    if env::var("CARGO_FEATURE_IMGUI").is_ok() {
+        make_sokol_imgui();
+    }
 }
@@ -1 +1,9 @@
+#[cfg(feature = "imgui")]
 pub mod imgui;
+
+/// Raw bindings to the types of the sokol headers, generated by bindgen. Regenerate them
+/// with the `bindgen` feature after updating the headers.
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]
+pub mod raw {
+    include!("sokol_raw.rs");
+}
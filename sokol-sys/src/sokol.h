@@ -0,0 +1,6 @@
+#include <sokol_app.h>
+#include <sokol_audio.h>
+#include <sokol_gfx.h>
+#include <sokol_time.h>
+#include <sokol_glue.h>
+#include <sokol_log.h>
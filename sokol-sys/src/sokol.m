@@ -1,10 +1,13 @@
 #define SOKOL_IMPL
-#define SOKOL_NO_ENTRY
 #define SOKOL_NO_DEPRECATED
 #define SOKOL_TRACE_HOOKS
-#include <sokol_app.h>
 #include <sokol_audio.h>
 #include <sokol_gfx.h>
 #include <sokol_time.h>
-#include <sokol_glue.h>
-#include <sokol_log.h>
\ No newline at end of file
+#include <sokol_log.h>
+
+/* the validation layer logs the items between these two, see sokol::gfx::SgTraceHooks */
+bool sokol_sys_sg_is_validation_log_item(uint32_t log_item_id) {
+    return log_item_id >= SG_LOGITEM_VALIDATE_BUFFERDESC_CANARY
+        && log_item_id <= SG_LOGITEM_VALIDATION_FAILED;
+}
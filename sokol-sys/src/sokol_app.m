@@ -0,0 +1,7 @@
+#define SOKOL_APP_IMPL
+#define SOKOL_GLUE_IMPL
+#define SOKOL_NO_ENTRY
+#define SOKOL_NO_DEPRECATED
+#include <sokol_app.h>
+#include <sokol_gfx.h>
+#include <sokol_glue.h>
@@ -1,3 +1,5 @@
+#include <stdlib.h>
+
 #include <sokol_app.h>
 #include <sokol_gfx.h>
 
@@ -21,13 +23,15 @@ typedef struct {
 } sg_imgui_wrap_t;
 
 extern "C" void sg_imgui_wrap_init(sg_imgui_wrap_t* ctx) {
-    ctx->content = (sg_imgui_t*) _sg_imgui_alloc(sizeof(sg_imgui_t));
-    sg_imgui_init(ctx->content);
+    ctx->content = (sg_imgui_t*) calloc(1, sizeof(sg_imgui_t));
+    const sg_imgui_desc_t desc = { };
+    sg_imgui_init(ctx->content, &desc);
 }
 
 extern "C" void sg_imgui_wrap_discard(sg_imgui_wrap_t* ctx) {
     sg_imgui_discard(ctx->content);
-    _sg_imgui_free(ctx->content);
+    free(ctx->content);
+    ctx->content = NULL;
 }
 
 extern "C" void sg_imgui_wrap_draw(sg_imgui_wrap_t* ctx) {
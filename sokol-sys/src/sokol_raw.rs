@@ -0,0 +1,1242 @@
+/* automatically generated by rust-bindgen 0.65.1 */
+
+pub const SAPP_MAX_TOUCHPOINTS: _bindgen_ty_1 = 8;
+pub const SAPP_MAX_MOUSEBUTTONS: _bindgen_ty_1 = 3;
+pub const SAPP_MAX_KEYCODES: _bindgen_ty_1 = 512;
+pub const SAPP_MAX_ICONIMAGES: _bindgen_ty_1 = 8;
+pub type _bindgen_ty_1 = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_range {
+    pub ptr: *const ::std::os::raw::c_void,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_image_desc {
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+    pub pixels: sapp_range,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_icon_desc {
+    pub sokol_default: bool,
+    pub images: [sapp_image_desc; 8usize],
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sapp_event_type {
+    SAPP_EVENTTYPE_INVALID = 0,
+    SAPP_EVENTTYPE_KEY_DOWN = 1,
+    SAPP_EVENTTYPE_KEY_UP = 2,
+    SAPP_EVENTTYPE_CHAR = 3,
+    SAPP_EVENTTYPE_MOUSE_DOWN = 4,
+    SAPP_EVENTTYPE_MOUSE_UP = 5,
+    SAPP_EVENTTYPE_MOUSE_SCROLL = 6,
+    SAPP_EVENTTYPE_MOUSE_MOVE = 7,
+    SAPP_EVENTTYPE_MOUSE_ENTER = 8,
+    SAPP_EVENTTYPE_MOUSE_LEAVE = 9,
+    SAPP_EVENTTYPE_TOUCHES_BEGAN = 10,
+    SAPP_EVENTTYPE_TOUCHES_MOVED = 11,
+    SAPP_EVENTTYPE_TOUCHES_ENDED = 12,
+    SAPP_EVENTTYPE_TOUCHES_CANCELLED = 13,
+    SAPP_EVENTTYPE_RESIZED = 14,
+    SAPP_EVENTTYPE_ICONIFIED = 15,
+    SAPP_EVENTTYPE_RESTORED = 16,
+    SAPP_EVENTTYPE_SUSPENDED = 17,
+    SAPP_EVENTTYPE_RESUMED = 18,
+    SAPP_EVENTTYPE_UPDATE_CURSOR = 19,
+    SAPP_EVENTTYPE_QUIT_REQUESTED = 20,
+    SAPP_EVENTTYPE_CLIPBOARD_PASTED = 21,
+    SAPP_EVENTTYPE_FILES_DROPPED = 22,
+    _SAPP_EVENTTYPE_NUM = 23,
+    _SAPP_EVENTTYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sapp_keycode {
+    SAPP_KEYCODE_INVALID = 0,
+    SAPP_KEYCODE_SPACE = 32,
+    SAPP_KEYCODE_APOSTROPHE = 39,
+    SAPP_KEYCODE_COMMA = 44,
+    SAPP_KEYCODE_MINUS = 45,
+    SAPP_KEYCODE_PERIOD = 46,
+    SAPP_KEYCODE_SLASH = 47,
+    SAPP_KEYCODE_0 = 48,
+    SAPP_KEYCODE_1 = 49,
+    SAPP_KEYCODE_2 = 50,
+    SAPP_KEYCODE_3 = 51,
+    SAPP_KEYCODE_4 = 52,
+    SAPP_KEYCODE_5 = 53,
+    SAPP_KEYCODE_6 = 54,
+    SAPP_KEYCODE_7 = 55,
+    SAPP_KEYCODE_8 = 56,
+    SAPP_KEYCODE_9 = 57,
+    SAPP_KEYCODE_SEMICOLON = 59,
+    SAPP_KEYCODE_EQUAL = 61,
+    SAPP_KEYCODE_A = 65,
+    SAPP_KEYCODE_B = 66,
+    SAPP_KEYCODE_C = 67,
+    SAPP_KEYCODE_D = 68,
+    SAPP_KEYCODE_E = 69,
+    SAPP_KEYCODE_F = 70,
+    SAPP_KEYCODE_G = 71,
+    SAPP_KEYCODE_H = 72,
+    SAPP_KEYCODE_I = 73,
+    SAPP_KEYCODE_J = 74,
+    SAPP_KEYCODE_K = 75,
+    SAPP_KEYCODE_L = 76,
+    SAPP_KEYCODE_M = 77,
+    SAPP_KEYCODE_N = 78,
+    SAPP_KEYCODE_O = 79,
+    SAPP_KEYCODE_P = 80,
+    SAPP_KEYCODE_Q = 81,
+    SAPP_KEYCODE_R = 82,
+    SAPP_KEYCODE_S = 83,
+    SAPP_KEYCODE_T = 84,
+    SAPP_KEYCODE_U = 85,
+    SAPP_KEYCODE_V = 86,
+    SAPP_KEYCODE_W = 87,
+    SAPP_KEYCODE_X = 88,
+    SAPP_KEYCODE_Y = 89,
+    SAPP_KEYCODE_Z = 90,
+    SAPP_KEYCODE_LEFT_BRACKET = 91,
+    SAPP_KEYCODE_BACKSLASH = 92,
+    SAPP_KEYCODE_RIGHT_BRACKET = 93,
+    SAPP_KEYCODE_GRAVE_ACCENT = 96,
+    SAPP_KEYCODE_WORLD_1 = 161,
+    SAPP_KEYCODE_WORLD_2 = 162,
+    SAPP_KEYCODE_ESCAPE = 256,
+    SAPP_KEYCODE_ENTER = 257,
+    SAPP_KEYCODE_TAB = 258,
+    SAPP_KEYCODE_BACKSPACE = 259,
+    SAPP_KEYCODE_INSERT = 260,
+    SAPP_KEYCODE_DELETE = 261,
+    SAPP_KEYCODE_RIGHT = 262,
+    SAPP_KEYCODE_LEFT = 263,
+    SAPP_KEYCODE_DOWN = 264,
+    SAPP_KEYCODE_UP = 265,
+    SAPP_KEYCODE_PAGE_UP = 266,
+    SAPP_KEYCODE_PAGE_DOWN = 267,
+    SAPP_KEYCODE_HOME = 268,
+    SAPP_KEYCODE_END = 269,
+    SAPP_KEYCODE_CAPS_LOCK = 280,
+    SAPP_KEYCODE_SCROLL_LOCK = 281,
+    SAPP_KEYCODE_NUM_LOCK = 282,
+    SAPP_KEYCODE_PRINT_SCREEN = 283,
+    SAPP_KEYCODE_PAUSE = 284,
+    SAPP_KEYCODE_F1 = 290,
+    SAPP_KEYCODE_F2 = 291,
+    SAPP_KEYCODE_F3 = 292,
+    SAPP_KEYCODE_F4 = 293,
+    SAPP_KEYCODE_F5 = 294,
+    SAPP_KEYCODE_F6 = 295,
+    SAPP_KEYCODE_F7 = 296,
+    SAPP_KEYCODE_F8 = 297,
+    SAPP_KEYCODE_F9 = 298,
+    SAPP_KEYCODE_F10 = 299,
+    SAPP_KEYCODE_F11 = 300,
+    SAPP_KEYCODE_F12 = 301,
+    SAPP_KEYCODE_F13 = 302,
+    SAPP_KEYCODE_F14 = 303,
+    SAPP_KEYCODE_F15 = 304,
+    SAPP_KEYCODE_F16 = 305,
+    SAPP_KEYCODE_F17 = 306,
+    SAPP_KEYCODE_F18 = 307,
+    SAPP_KEYCODE_F19 = 308,
+    SAPP_KEYCODE_F20 = 309,
+    SAPP_KEYCODE_F21 = 310,
+    SAPP_KEYCODE_F22 = 311,
+    SAPP_KEYCODE_F23 = 312,
+    SAPP_KEYCODE_F24 = 313,
+    SAPP_KEYCODE_F25 = 314,
+    SAPP_KEYCODE_KP_0 = 320,
+    SAPP_KEYCODE_KP_1 = 321,
+    SAPP_KEYCODE_KP_2 = 322,
+    SAPP_KEYCODE_KP_3 = 323,
+    SAPP_KEYCODE_KP_4 = 324,
+    SAPP_KEYCODE_KP_5 = 325,
+    SAPP_KEYCODE_KP_6 = 326,
+    SAPP_KEYCODE_KP_7 = 327,
+    SAPP_KEYCODE_KP_8 = 328,
+    SAPP_KEYCODE_KP_9 = 329,
+    SAPP_KEYCODE_KP_DECIMAL = 330,
+    SAPP_KEYCODE_KP_DIVIDE = 331,
+    SAPP_KEYCODE_KP_MULTIPLY = 332,
+    SAPP_KEYCODE_KP_SUBTRACT = 333,
+    SAPP_KEYCODE_KP_ADD = 334,
+    SAPP_KEYCODE_KP_ENTER = 335,
+    SAPP_KEYCODE_KP_EQUAL = 336,
+    SAPP_KEYCODE_LEFT_SHIFT = 340,
+    SAPP_KEYCODE_LEFT_CONTROL = 341,
+    SAPP_KEYCODE_LEFT_ALT = 342,
+    SAPP_KEYCODE_LEFT_SUPER = 343,
+    SAPP_KEYCODE_RIGHT_SHIFT = 344,
+    SAPP_KEYCODE_RIGHT_CONTROL = 345,
+    SAPP_KEYCODE_RIGHT_ALT = 346,
+    SAPP_KEYCODE_RIGHT_SUPER = 347,
+    SAPP_KEYCODE_MENU = 348,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_touchpoint {
+    pub identifier: usize,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub changed: bool,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sapp_mousebutton {
+    SAPP_MOUSEBUTTON_LEFT = 0,
+    SAPP_MOUSEBUTTON_RIGHT = 1,
+    SAPP_MOUSEBUTTON_MIDDLE = 2,
+    SAPP_MOUSEBUTTON_INVALID = 256,
+}
+pub const SAPP_MODIFIER_SHIFT: _bindgen_ty_2 = 1;
+pub const SAPP_MODIFIER_CTRL: _bindgen_ty_2 = 2;
+pub const SAPP_MODIFIER_ALT: _bindgen_ty_2 = 4;
+pub const SAPP_MODIFIER_SUPER: _bindgen_ty_2 = 8;
+pub const SAPP_MODIFIER_LMB: _bindgen_ty_2 = 256;
+pub const SAPP_MODIFIER_RMB: _bindgen_ty_2 = 512;
+pub const SAPP_MODIFIER_MMB: _bindgen_ty_2 = 1024;
+pub type _bindgen_ty_2 = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_event {
+    pub frame_count: u64,
+    pub type_: sapp_event_type,
+    pub key_code: sapp_keycode,
+    pub char_code: u32,
+    pub key_repeat: bool,
+    pub modifiers: u32,
+    pub mouse_button: sapp_mousebutton,
+    pub mouse_x: f32,
+    pub mouse_y: f32,
+    pub mouse_dx: f32,
+    pub mouse_dy: f32,
+    pub scroll_x: f32,
+    pub scroll_y: f32,
+    pub num_touches: ::std::os::raw::c_int,
+    pub touches: [sapp_touchpoint; 8usize],
+    pub window_width: ::std::os::raw::c_int,
+    pub window_height: ::std::os::raw::c_int,
+    pub framebuffer_width: ::std::os::raw::c_int,
+    pub framebuffer_height: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_allocator {
+    pub alloc: ::std::option::Option<unsafe extern "C" fn(size: usize, user_data: *mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void>,
+    pub free: ::std::option::Option<unsafe extern "C" fn(ptr: *mut ::std::os::raw::c_void, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_logger {
+    pub func: ::std::option::Option<unsafe extern "C" fn(tag: *const ::std::os::raw::c_char, log_level: u32, log_item_id: u32, message_or_null: *const ::std::os::raw::c_char, line_nr: u32, filename_or_null: *const ::std::os::raw::c_char, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sapp_desc {
+    pub init_cb: ::std::option::Option<unsafe extern "C" fn()>,
+    pub frame_cb: ::std::option::Option<unsafe extern "C" fn()>,
+    pub cleanup_cb: ::std::option::Option<unsafe extern "C" fn()>,
+    pub event_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *const sapp_event)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+    pub init_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub frame_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub cleanup_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void)>,
+    pub event_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *const sapp_event, arg2: *mut ::std::os::raw::c_void)>,
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+    pub sample_count: ::std::os::raw::c_int,
+    pub swap_interval: ::std::os::raw::c_int,
+    pub high_dpi: bool,
+    pub fullscreen: bool,
+    pub alpha: bool,
+    pub window_title: *const ::std::os::raw::c_char,
+    pub enable_clipboard: bool,
+    pub clipboard_size: ::std::os::raw::c_int,
+    pub enable_dragndrop: bool,
+    pub max_dropped_files: ::std::os::raw::c_int,
+    pub max_dropped_file_path_length: ::std::os::raw::c_int,
+    pub icon: sapp_icon_desc,
+    pub allocator: sapp_allocator,
+    pub logger: sapp_logger,
+    pub gl_force_gles2: bool,
+    pub gl_major_version: ::std::os::raw::c_int,
+    pub gl_minor_version: ::std::os::raw::c_int,
+    pub win32_console_utf8: bool,
+    pub win32_console_create: bool,
+    pub win32_console_attach: bool,
+    pub html5_canvas_name: *const ::std::os::raw::c_char,
+    pub html5_canvas_resize: bool,
+    pub html5_preserve_drawing_buffer: bool,
+    pub html5_premultiplied_alpha: bool,
+    pub html5_ask_leave_site: bool,
+    pub ios_keyboard_resizes_canvas: bool,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct saudio_allocator {
+    pub alloc: ::std::option::Option<unsafe extern "C" fn(size: usize, user_data: *mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void>,
+    pub free: ::std::option::Option<unsafe extern "C" fn(ptr: *mut ::std::os::raw::c_void, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct saudio_logger {
+    pub func: ::std::option::Option<unsafe extern "C" fn(tag: *const ::std::os::raw::c_char, log_level: u32, log_item_id: u32, message_or_null: *const ::std::os::raw::c_char, line_nr: u32, filename_or_null: *const ::std::os::raw::c_char, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct saudio_desc {
+    pub sample_rate: ::std::os::raw::c_int,
+    pub num_channels: ::std::os::raw::c_int,
+    pub buffer_frames: ::std::os::raw::c_int,
+    pub packet_frames: ::std::os::raw::c_int,
+    pub num_packets: ::std::os::raw::c_int,
+    pub stream_cb: ::std::option::Option<unsafe extern "C" fn(buffer: *mut f32, num_frames: ::std::os::raw::c_int, num_channels: ::std::os::raw::c_int)>,
+    pub stream_userdata_cb: ::std::option::Option<unsafe extern "C" fn(buffer: *mut f32, num_frames: ::std::os::raw::c_int, num_channels: ::std::os::raw::c_int, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+    pub allocator: saudio_allocator,
+    pub logger: saudio_logger,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_buffer {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_image {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pipeline {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pass {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_context {
+    pub id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_range {
+    pub ptr: *const ::std::os::raw::c_void,
+    pub size: usize,
+}
+pub const SG_INVALID_ID: _bindgen_ty_3 = 0;
+pub const SG_NUM_SHADER_STAGES: _bindgen_ty_3 = 2;
+pub const SG_NUM_INFLIGHT_FRAMES: _bindgen_ty_3 = 2;
+pub const SG_MAX_COLOR_ATTACHMENTS: _bindgen_ty_3 = 4;
+pub const SG_MAX_SHADERSTAGE_BUFFERS: _bindgen_ty_3 = 8;
+pub const SG_MAX_SHADERSTAGE_IMAGES: _bindgen_ty_3 = 12;
+pub const SG_MAX_SHADERSTAGE_UBS: _bindgen_ty_3 = 4;
+pub const SG_MAX_UB_MEMBERS: _bindgen_ty_3 = 16;
+pub const SG_MAX_VERTEX_ATTRIBUTES: _bindgen_ty_3 = 16;
+pub const SG_MAX_MIPMAPS: _bindgen_ty_3 = 16;
+pub const SG_MAX_TEXTUREARRAY_LAYERS: _bindgen_ty_3 = 128;
+pub type _bindgen_ty_3 = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_backend {
+    SG_BACKEND_GLCORE33 = 0,
+    SG_BACKEND_GLES2 = 1,
+    SG_BACKEND_GLES3 = 2,
+    SG_BACKEND_D3D11 = 3,
+    SG_BACKEND_METAL_IOS = 4,
+    SG_BACKEND_METAL_MACOS = 5,
+    SG_BACKEND_METAL_SIMULATOR = 6,
+    SG_BACKEND_WGPU = 7,
+    SG_BACKEND_DUMMY = 8,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_pixel_format {
+    _SG_PIXELFORMAT_DEFAULT = 0,
+    SG_PIXELFORMAT_NONE = 1,
+    SG_PIXELFORMAT_R8 = 2,
+    SG_PIXELFORMAT_R8SN = 3,
+    SG_PIXELFORMAT_R8UI = 4,
+    SG_PIXELFORMAT_R8SI = 5,
+    SG_PIXELFORMAT_R16 = 6,
+    SG_PIXELFORMAT_R16SN = 7,
+    SG_PIXELFORMAT_R16UI = 8,
+    SG_PIXELFORMAT_R16SI = 9,
+    SG_PIXELFORMAT_R16F = 10,
+    SG_PIXELFORMAT_RG8 = 11,
+    SG_PIXELFORMAT_RG8SN = 12,
+    SG_PIXELFORMAT_RG8UI = 13,
+    SG_PIXELFORMAT_RG8SI = 14,
+    SG_PIXELFORMAT_R32UI = 15,
+    SG_PIXELFORMAT_R32SI = 16,
+    SG_PIXELFORMAT_R32F = 17,
+    SG_PIXELFORMAT_RG16 = 18,
+    SG_PIXELFORMAT_RG16SN = 19,
+    SG_PIXELFORMAT_RG16UI = 20,
+    SG_PIXELFORMAT_RG16SI = 21,
+    SG_PIXELFORMAT_RG16F = 22,
+    SG_PIXELFORMAT_RGBA8 = 23,
+    SG_PIXELFORMAT_SRGB8A8 = 24,
+    SG_PIXELFORMAT_RGBA8SN = 25,
+    SG_PIXELFORMAT_RGBA8UI = 26,
+    SG_PIXELFORMAT_RGBA8SI = 27,
+    SG_PIXELFORMAT_BGRA8 = 28,
+    SG_PIXELFORMAT_RGB10A2 = 29,
+    SG_PIXELFORMAT_RG11B10F = 30,
+    SG_PIXELFORMAT_RG32UI = 31,
+    SG_PIXELFORMAT_RG32SI = 32,
+    SG_PIXELFORMAT_RG32F = 33,
+    SG_PIXELFORMAT_RGBA16 = 34,
+    SG_PIXELFORMAT_RGBA16SN = 35,
+    SG_PIXELFORMAT_RGBA16UI = 36,
+    SG_PIXELFORMAT_RGBA16SI = 37,
+    SG_PIXELFORMAT_RGBA16F = 38,
+    SG_PIXELFORMAT_RGBA32UI = 39,
+    SG_PIXELFORMAT_RGBA32SI = 40,
+    SG_PIXELFORMAT_RGBA32F = 41,
+    SG_PIXELFORMAT_DEPTH = 42,
+    SG_PIXELFORMAT_DEPTH_STENCIL = 43,
+    SG_PIXELFORMAT_BC1_RGBA = 44,
+    SG_PIXELFORMAT_BC2_RGBA = 45,
+    SG_PIXELFORMAT_BC3_RGBA = 46,
+    SG_PIXELFORMAT_BC4_R = 47,
+    SG_PIXELFORMAT_BC4_RSN = 48,
+    SG_PIXELFORMAT_BC5_RG = 49,
+    SG_PIXELFORMAT_BC5_RGSN = 50,
+    SG_PIXELFORMAT_BC6H_RGBF = 51,
+    SG_PIXELFORMAT_BC6H_RGBUF = 52,
+    SG_PIXELFORMAT_BC7_RGBA = 53,
+    SG_PIXELFORMAT_PVRTC_RGB_2BPP = 54,
+    SG_PIXELFORMAT_PVRTC_RGB_4BPP = 55,
+    SG_PIXELFORMAT_PVRTC_RGBA_2BPP = 56,
+    SG_PIXELFORMAT_PVRTC_RGBA_4BPP = 57,
+    SG_PIXELFORMAT_ETC2_RGB8 = 58,
+    SG_PIXELFORMAT_ETC2_RGB8A1 = 59,
+    SG_PIXELFORMAT_ETC2_RGBA8 = 60,
+    SG_PIXELFORMAT_ETC2_RG11 = 61,
+    SG_PIXELFORMAT_ETC2_RG11SN = 62,
+    SG_PIXELFORMAT_RGB9E5 = 63,
+    _SG_PIXELFORMAT_NUM = 64,
+    _SG_PIXELFORMAT_FORCE_U32 = 2147483647,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pixelformat_info {
+    pub sample: bool,
+    pub filter: bool,
+    pub render: bool,
+    pub blend: bool,
+    pub msaa: bool,
+    pub depth: bool,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_features {
+    pub instancing: bool,
+    pub origin_top_left: bool,
+    pub multiple_render_targets: bool,
+    pub msaa_render_targets: bool,
+    pub imagetype_3d: bool,
+    pub imagetype_array: bool,
+    pub image_clamp_to_border: bool,
+    pub mrt_independent_blend_state: bool,
+    pub mrt_independent_write_mask: bool,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_limits {
+    pub max_image_size_2d: ::std::os::raw::c_int,
+    pub max_image_size_cube: ::std::os::raw::c_int,
+    pub max_image_size_3d: ::std::os::raw::c_int,
+    pub max_image_size_array: ::std::os::raw::c_int,
+    pub max_image_array_layers: ::std::os::raw::c_int,
+    pub max_vertex_attrs: ::std::os::raw::c_int,
+    pub gl_max_vertex_uniform_vectors: ::std::os::raw::c_int,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_resource_state {
+    SG_RESOURCESTATE_INITIAL = 0,
+    SG_RESOURCESTATE_ALLOC = 1,
+    SG_RESOURCESTATE_VALID = 2,
+    SG_RESOURCESTATE_FAILED = 3,
+    SG_RESOURCESTATE_INVALID = 4,
+    _SG_RESOURCESTATE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_usage {
+    _SG_USAGE_DEFAULT = 0,
+    SG_USAGE_IMMUTABLE = 1,
+    SG_USAGE_DYNAMIC = 2,
+    SG_USAGE_STREAM = 3,
+    _SG_USAGE_NUM = 4,
+    _SG_USAGE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_buffer_type {
+    _SG_BUFFERTYPE_DEFAULT = 0,
+    SG_BUFFERTYPE_VERTEXBUFFER = 1,
+    SG_BUFFERTYPE_INDEXBUFFER = 2,
+    _SG_BUFFERTYPE_NUM = 3,
+    _SG_BUFFERTYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_index_type {
+    _SG_INDEXTYPE_DEFAULT = 0,
+    SG_INDEXTYPE_NONE = 1,
+    SG_INDEXTYPE_UINT16 = 2,
+    SG_INDEXTYPE_UINT32 = 3,
+    _SG_INDEXTYPE_NUM = 4,
+    _SG_INDEXTYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_image_type {
+    _SG_IMAGETYPE_DEFAULT = 0,
+    SG_IMAGETYPE_2D = 1,
+    SG_IMAGETYPE_CUBE = 2,
+    SG_IMAGETYPE_3D = 3,
+    SG_IMAGETYPE_ARRAY = 4,
+    _SG_IMAGETYPE_NUM = 5,
+    _SG_IMAGETYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_sampler_type {
+    _SG_SAMPLERTYPE_DEFAULT = 0,
+    SG_SAMPLERTYPE_FLOAT = 1,
+    SG_SAMPLERTYPE_SINT = 2,
+    SG_SAMPLERTYPE_UINT = 3,
+    _SG_SAMPLERTYPE_NUM = 4,
+    _SG_SAMPLERTYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_cube_face {
+    SG_CUBEFACE_POS_X = 0,
+    SG_CUBEFACE_NEG_X = 1,
+    SG_CUBEFACE_POS_Y = 2,
+    SG_CUBEFACE_NEG_Y = 3,
+    SG_CUBEFACE_POS_Z = 4,
+    SG_CUBEFACE_NEG_Z = 5,
+    SG_CUBEFACE_NUM = 6,
+    _SG_CUBEFACE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_shader_stage {
+    SG_SHADERSTAGE_VS = 0,
+    SG_SHADERSTAGE_FS = 1,
+    _SG_SHADERSTAGE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_primitive_type {
+    _SG_PRIMITIVETYPE_DEFAULT = 0,
+    SG_PRIMITIVETYPE_POINTS = 1,
+    SG_PRIMITIVETYPE_LINES = 2,
+    SG_PRIMITIVETYPE_LINE_STRIP = 3,
+    SG_PRIMITIVETYPE_TRIANGLES = 4,
+    SG_PRIMITIVETYPE_TRIANGLE_STRIP = 5,
+    _SG_PRIMITIVETYPE_NUM = 6,
+    _SG_PRIMITIVETYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_filter {
+    _SG_FILTER_DEFAULT = 0,
+    SG_FILTER_NEAREST = 1,
+    SG_FILTER_LINEAR = 2,
+    SG_FILTER_NEAREST_MIPMAP_NEAREST = 3,
+    SG_FILTER_NEAREST_MIPMAP_LINEAR = 4,
+    SG_FILTER_LINEAR_MIPMAP_NEAREST = 5,
+    SG_FILTER_LINEAR_MIPMAP_LINEAR = 6,
+    _SG_FILTER_NUM = 7,
+    _SG_FILTER_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_wrap {
+    _SG_WRAP_DEFAULT = 0,
+    SG_WRAP_REPEAT = 1,
+    SG_WRAP_CLAMP_TO_EDGE = 2,
+    SG_WRAP_CLAMP_TO_BORDER = 3,
+    SG_WRAP_MIRRORED_REPEAT = 4,
+    _SG_WRAP_NUM = 5,
+    _SG_WRAP_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_border_color {
+    _SG_BORDERCOLOR_DEFAULT = 0,
+    SG_BORDERCOLOR_TRANSPARENT_BLACK = 1,
+    SG_BORDERCOLOR_OPAQUE_BLACK = 2,
+    SG_BORDERCOLOR_OPAQUE_WHITE = 3,
+    _SG_BORDERCOLOR_NUM = 4,
+    _SG_BORDERCOLOR_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_vertex_format {
+    SG_VERTEXFORMAT_INVALID = 0,
+    SG_VERTEXFORMAT_FLOAT = 1,
+    SG_VERTEXFORMAT_FLOAT2 = 2,
+    SG_VERTEXFORMAT_FLOAT3 = 3,
+    SG_VERTEXFORMAT_FLOAT4 = 4,
+    SG_VERTEXFORMAT_BYTE4 = 5,
+    SG_VERTEXFORMAT_BYTE4N = 6,
+    SG_VERTEXFORMAT_UBYTE4 = 7,
+    SG_VERTEXFORMAT_UBYTE4N = 8,
+    SG_VERTEXFORMAT_SHORT2 = 9,
+    SG_VERTEXFORMAT_SHORT2N = 10,
+    SG_VERTEXFORMAT_USHORT2N = 11,
+    SG_VERTEXFORMAT_SHORT4 = 12,
+    SG_VERTEXFORMAT_SHORT4N = 13,
+    SG_VERTEXFORMAT_USHORT4N = 14,
+    SG_VERTEXFORMAT_UINT10_N2 = 15,
+    SG_VERTEXFORMAT_HALF2 = 16,
+    SG_VERTEXFORMAT_HALF4 = 17,
+    _SG_VERTEXFORMAT_NUM = 18,
+    _SG_VERTEXFORMAT_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_vertex_step {
+    _SG_VERTEXSTEP_DEFAULT = 0,
+    SG_VERTEXSTEP_PER_VERTEX = 1,
+    SG_VERTEXSTEP_PER_INSTANCE = 2,
+    _SG_VERTEXSTEP_NUM = 3,
+    _SG_VERTEXSTEP_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_uniform_type {
+    SG_UNIFORMTYPE_INVALID = 0,
+    SG_UNIFORMTYPE_FLOAT = 1,
+    SG_UNIFORMTYPE_FLOAT2 = 2,
+    SG_UNIFORMTYPE_FLOAT3 = 3,
+    SG_UNIFORMTYPE_FLOAT4 = 4,
+    SG_UNIFORMTYPE_INT = 5,
+    SG_UNIFORMTYPE_INT2 = 6,
+    SG_UNIFORMTYPE_INT3 = 7,
+    SG_UNIFORMTYPE_INT4 = 8,
+    SG_UNIFORMTYPE_MAT4 = 9,
+    _SG_UNIFORMTYPE_NUM = 10,
+    _SG_UNIFORMTYPE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_uniform_layout {
+    _SG_UNIFORMLAYOUT_DEFAULT = 0,
+    SG_UNIFORMLAYOUT_NATIVE = 1,
+    SG_UNIFORMLAYOUT_STD140 = 2,
+    _SG_UNIFORMLAYOUT_NUM = 3,
+    _SG_UNIFORMLAYOUT_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_cull_mode {
+    _SG_CULLMODE_DEFAULT = 0,
+    SG_CULLMODE_NONE = 1,
+    SG_CULLMODE_FRONT = 2,
+    SG_CULLMODE_BACK = 3,
+    _SG_CULLMODE_NUM = 4,
+    _SG_CULLMODE_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_face_winding {
+    _SG_FACEWINDING_DEFAULT = 0,
+    SG_FACEWINDING_CCW = 1,
+    SG_FACEWINDING_CW = 2,
+    _SG_FACEWINDING_NUM = 3,
+    _SG_FACEWINDING_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_compare_func {
+    _SG_COMPAREFUNC_DEFAULT = 0,
+    SG_COMPAREFUNC_NEVER = 1,
+    SG_COMPAREFUNC_LESS = 2,
+    SG_COMPAREFUNC_EQUAL = 3,
+    SG_COMPAREFUNC_LESS_EQUAL = 4,
+    SG_COMPAREFUNC_GREATER = 5,
+    SG_COMPAREFUNC_NOT_EQUAL = 6,
+    SG_COMPAREFUNC_GREATER_EQUAL = 7,
+    SG_COMPAREFUNC_ALWAYS = 8,
+    _SG_COMPAREFUNC_NUM = 9,
+    _SG_COMPAREFUNC_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_stencil_op {
+    _SG_STENCILOP_DEFAULT = 0,
+    SG_STENCILOP_KEEP = 1,
+    SG_STENCILOP_ZERO = 2,
+    SG_STENCILOP_REPLACE = 3,
+    SG_STENCILOP_INCR_CLAMP = 4,
+    SG_STENCILOP_DECR_CLAMP = 5,
+    SG_STENCILOP_INVERT = 6,
+    SG_STENCILOP_INCR_WRAP = 7,
+    SG_STENCILOP_DECR_WRAP = 8,
+    _SG_STENCILOP_NUM = 9,
+    _SG_STENCILOP_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_blend_factor {
+    _SG_BLENDFACTOR_DEFAULT = 0,
+    SG_BLENDFACTOR_ZERO = 1,
+    SG_BLENDFACTOR_ONE = 2,
+    SG_BLENDFACTOR_SRC_COLOR = 3,
+    SG_BLENDFACTOR_ONE_MINUS_SRC_COLOR = 4,
+    SG_BLENDFACTOR_SRC_ALPHA = 5,
+    SG_BLENDFACTOR_ONE_MINUS_SRC_ALPHA = 6,
+    SG_BLENDFACTOR_DST_COLOR = 7,
+    SG_BLENDFACTOR_ONE_MINUS_DST_COLOR = 8,
+    SG_BLENDFACTOR_DST_ALPHA = 9,
+    SG_BLENDFACTOR_ONE_MINUS_DST_ALPHA = 10,
+    SG_BLENDFACTOR_SRC_ALPHA_SATURATED = 11,
+    SG_BLENDFACTOR_BLEND_COLOR = 12,
+    SG_BLENDFACTOR_ONE_MINUS_BLEND_COLOR = 13,
+    SG_BLENDFACTOR_BLEND_ALPHA = 14,
+    SG_BLENDFACTOR_ONE_MINUS_BLEND_ALPHA = 15,
+    _SG_BLENDFACTOR_NUM = 16,
+    _SG_BLENDFACTOR_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_blend_op {
+    _SG_BLENDOP_DEFAULT = 0,
+    SG_BLENDOP_ADD = 1,
+    SG_BLENDOP_SUBTRACT = 2,
+    SG_BLENDOP_REVERSE_SUBTRACT = 3,
+    _SG_BLENDOP_NUM = 4,
+    _SG_BLENDOP_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_color_mask {
+    _SG_COLORMASK_DEFAULT = 0,
+    SG_COLORMASK_NONE = 16,
+    SG_COLORMASK_R = 1,
+    SG_COLORMASK_G = 2,
+    SG_COLORMASK_RG = 3,
+    SG_COLORMASK_B = 4,
+    SG_COLORMASK_RB = 5,
+    SG_COLORMASK_GB = 6,
+    SG_COLORMASK_RGB = 7,
+    SG_COLORMASK_A = 8,
+    SG_COLORMASK_RA = 9,
+    SG_COLORMASK_GA = 10,
+    SG_COLORMASK_RGA = 11,
+    SG_COLORMASK_BA = 12,
+    SG_COLORMASK_RBA = 13,
+    SG_COLORMASK_GBA = 14,
+    SG_COLORMASK_RGBA = 15,
+    _SG_COLORMASK_FORCE_U32 = 2147483647,
+}
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum sg_action {
+    _SG_ACTION_DEFAULT = 0,
+    SG_ACTION_CLEAR = 1,
+    SG_ACTION_LOAD = 2,
+    SG_ACTION_DONTCARE = 3,
+    _SG_ACTION_NUM = 4,
+    _SG_ACTION_FORCE_U32 = 2147483647,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_color_attachment_action {
+    pub action: sg_action,
+    pub value: sg_color,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_depth_attachment_action {
+    pub action: sg_action,
+    pub value: f32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_stencil_attachment_action {
+    pub action: sg_action,
+    pub value: u8,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pass_action {
+    pub _start_canary: u32,
+    pub colors: [sg_color_attachment_action; 4usize],
+    pub depth: sg_depth_attachment_action,
+    pub stencil: sg_stencil_attachment_action,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_bindings {
+    pub _start_canary: u32,
+    pub vertex_buffers: [sg_buffer; 8usize],
+    pub vertex_buffer_offsets: [::std::os::raw::c_int; 8usize],
+    pub index_buffer: sg_buffer,
+    pub index_buffer_offset: ::std::os::raw::c_int,
+    pub vs_images: [sg_image; 12usize],
+    pub fs_images: [sg_image; 12usize],
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_buffer_desc {
+    pub _start_canary: u32,
+    pub size: usize,
+    pub type_: sg_buffer_type,
+    pub usage: sg_usage,
+    pub data: sg_range,
+    pub label: *const ::std::os::raw::c_char,
+    pub gl_buffers: [u32; 2usize],
+    pub mtl_buffers: [*const ::std::os::raw::c_void; 2usize],
+    pub d3d11_buffer: *const ::std::os::raw::c_void,
+    pub wgpu_buffer: *const ::std::os::raw::c_void,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_image_data {
+    pub subimage: [[sg_range; 16usize]; 6usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_image_desc {
+    pub _start_canary: u32,
+    pub type_: sg_image_type,
+    pub render_target: bool,
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+    pub num_slices: ::std::os::raw::c_int,
+    pub num_mipmaps: ::std::os::raw::c_int,
+    pub usage: sg_usage,
+    pub pixel_format: sg_pixel_format,
+    pub sample_count: ::std::os::raw::c_int,
+    pub min_filter: sg_filter,
+    pub mag_filter: sg_filter,
+    pub wrap_u: sg_wrap,
+    pub wrap_v: sg_wrap,
+    pub wrap_w: sg_wrap,
+    pub border_color: sg_border_color,
+    pub max_anisotropy: u32,
+    pub min_lod: f32,
+    pub max_lod: f32,
+    pub data: sg_image_data,
+    pub label: *const ::std::os::raw::c_char,
+    pub gl_textures: [u32; 2usize],
+    pub gl_texture_target: u32,
+    pub mtl_textures: [*const ::std::os::raw::c_void; 2usize],
+    pub d3d11_texture: *const ::std::os::raw::c_void,
+    pub d3d11_shader_resource_view: *const ::std::os::raw::c_void,
+    pub wgpu_texture: *const ::std::os::raw::c_void,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_attr_desc {
+    pub name: *const ::std::os::raw::c_char,
+    pub sem_name: *const ::std::os::raw::c_char,
+    pub sem_index: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_uniform_desc {
+    pub name: *const ::std::os::raw::c_char,
+    pub type_: sg_uniform_type,
+    pub array_count: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_uniform_block_desc {
+    pub size: usize,
+    pub layout: sg_uniform_layout,
+    pub uniforms: [sg_shader_uniform_desc; 16usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_image_desc {
+    pub name: *const ::std::os::raw::c_char,
+    pub image_type: sg_image_type,
+    pub sampler_type: sg_sampler_type,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_stage_desc {
+    pub source: *const ::std::os::raw::c_char,
+    pub bytecode: sg_range,
+    pub entry: *const ::std::os::raw::c_char,
+    pub d3d11_target: *const ::std::os::raw::c_char,
+    pub uniform_blocks: [sg_shader_uniform_block_desc; 4usize],
+    pub images: [sg_shader_image_desc; 12usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_desc {
+    pub _start_canary: u32,
+    pub attrs: [sg_shader_attr_desc; 16usize],
+    pub vs: sg_shader_stage_desc,
+    pub fs: sg_shader_stage_desc,
+    pub label: *const ::std::os::raw::c_char,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_buffer_layout_desc {
+    pub stride: ::std::os::raw::c_int,
+    pub step_func: sg_vertex_step,
+    pub step_rate: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_vertex_attr_desc {
+    pub buffer_index: ::std::os::raw::c_int,
+    pub offset: ::std::os::raw::c_int,
+    pub format: sg_vertex_format,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_layout_desc {
+    pub buffers: [sg_buffer_layout_desc; 8usize],
+    pub attrs: [sg_vertex_attr_desc; 16usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_stencil_face_state {
+    pub compare: sg_compare_func,
+    pub fail_op: sg_stencil_op,
+    pub depth_fail_op: sg_stencil_op,
+    pub pass_op: sg_stencil_op,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_stencil_state {
+    pub enabled: bool,
+    pub front: sg_stencil_face_state,
+    pub back: sg_stencil_face_state,
+    pub read_mask: u8,
+    pub write_mask: u8,
+    pub ref_: u8,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_depth_state {
+    pub pixel_format: sg_pixel_format,
+    pub compare: sg_compare_func,
+    pub write_enabled: bool,
+    pub bias: f32,
+    pub bias_slope_scale: f32,
+    pub bias_clamp: f32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_blend_state {
+    pub enabled: bool,
+    pub src_factor_rgb: sg_blend_factor,
+    pub dst_factor_rgb: sg_blend_factor,
+    pub op_rgb: sg_blend_op,
+    pub src_factor_alpha: sg_blend_factor,
+    pub dst_factor_alpha: sg_blend_factor,
+    pub op_alpha: sg_blend_op,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_color_state {
+    pub pixel_format: sg_pixel_format,
+    pub write_mask: sg_color_mask,
+    pub blend: sg_blend_state,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pipeline_desc {
+    pub _start_canary: u32,
+    pub shader: sg_shader,
+    pub layout: sg_layout_desc,
+    pub depth: sg_depth_state,
+    pub stencil: sg_stencil_state,
+    pub color_count: ::std::os::raw::c_int,
+    pub colors: [sg_color_state; 4usize],
+    pub primitive_type: sg_primitive_type,
+    pub index_type: sg_index_type,
+    pub cull_mode: sg_cull_mode,
+    pub face_winding: sg_face_winding,
+    pub sample_count: ::std::os::raw::c_int,
+    pub blend_color: sg_color,
+    pub alpha_to_coverage_enabled: bool,
+    pub label: *const ::std::os::raw::c_char,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pass_attachment_desc {
+    pub image: sg_image,
+    pub mip_level: ::std::os::raw::c_int,
+    pub slice: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pass_desc {
+    pub _start_canary: u32,
+    pub color_attachments: [sg_pass_attachment_desc; 4usize],
+    pub depth_stencil_attachment: sg_pass_attachment_desc,
+    pub label: *const ::std::os::raw::c_char,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_trace_hooks {
+    pub user_data: *mut ::std::os::raw::c_void,
+    pub reset_state_cache: ::std::option::Option<unsafe extern "C" fn(user_data: *mut ::std::os::raw::c_void)>,
+    pub make_buffer: ::std::option::Option<unsafe extern "C" fn(desc: *const sg_buffer_desc, result: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub make_image: ::std::option::Option<unsafe extern "C" fn(desc: *const sg_image_desc, result: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub make_shader: ::std::option::Option<unsafe extern "C" fn(desc: *const sg_shader_desc, result: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub make_pipeline: ::std::option::Option<unsafe extern "C" fn(desc: *const sg_pipeline_desc, result: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub make_pass: ::std::option::Option<unsafe extern "C" fn(desc: *const sg_pass_desc, result: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub destroy_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub destroy_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub destroy_shader: ::std::option::Option<unsafe extern "C" fn(shd: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub destroy_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub destroy_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub update_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, data: *const sg_range, user_data: *mut ::std::os::raw::c_void)>,
+    pub update_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, data: *const sg_image_data, user_data: *mut ::std::os::raw::c_void)>,
+    pub append_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, data: *const sg_range, result: ::std::os::raw::c_int, user_data: *mut ::std::os::raw::c_void)>,
+    pub begin_default_pass: ::std::option::Option<unsafe extern "C" fn(pass_action: *const sg_pass_action, width: ::std::os::raw::c_int, height: ::std::os::raw::c_int, user_data: *mut ::std::os::raw::c_void)>,
+    pub begin_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, pass_action: *const sg_pass_action, user_data: *mut ::std::os::raw::c_void)>,
+    pub apply_viewport: ::std::option::Option<unsafe extern "C" fn(x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, width: ::std::os::raw::c_int, height: ::std::os::raw::c_int, origin_top_left: bool, user_data: *mut ::std::os::raw::c_void)>,
+    pub apply_scissor_rect: ::std::option::Option<unsafe extern "C" fn(x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, width: ::std::os::raw::c_int, height: ::std::os::raw::c_int, origin_top_left: bool, user_data: *mut ::std::os::raw::c_void)>,
+    pub apply_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub apply_bindings: ::std::option::Option<unsafe extern "C" fn(bindings: *const sg_bindings, user_data: *mut ::std::os::raw::c_void)>,
+    pub apply_uniforms: ::std::option::Option<unsafe extern "C" fn(stage: sg_shader_stage, ub_index: ::std::os::raw::c_int, data: *const sg_range, user_data: *mut ::std::os::raw::c_void)>,
+    pub draw: ::std::option::Option<unsafe extern "C" fn(base_element: ::std::os::raw::c_int, num_elements: ::std::os::raw::c_int, num_instances: ::std::os::raw::c_int, user_data: *mut ::std::os::raw::c_void)>,
+    pub end_pass: ::std::option::Option<unsafe extern "C" fn(user_data: *mut ::std::os::raw::c_void)>,
+    pub commit: ::std::option::Option<unsafe extern "C" fn(user_data: *mut ::std::os::raw::c_void)>,
+    pub alloc_buffer: ::std::option::Option<unsafe extern "C" fn(result: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub alloc_image: ::std::option::Option<unsafe extern "C" fn(result: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub alloc_shader: ::std::option::Option<unsafe extern "C" fn(result: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub alloc_pipeline: ::std::option::Option<unsafe extern "C" fn(result: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub alloc_pass: ::std::option::Option<unsafe extern "C" fn(result: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub dealloc_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub dealloc_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub dealloc_shader: ::std::option::Option<unsafe extern "C" fn(shd: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub dealloc_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub dealloc_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub init_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, desc: *const sg_buffer_desc, user_data: *mut ::std::os::raw::c_void)>,
+    pub init_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, desc: *const sg_image_desc, user_data: *mut ::std::os::raw::c_void)>,
+    pub init_shader: ::std::option::Option<unsafe extern "C" fn(shd: sg_shader, desc: *const sg_shader_desc, user_data: *mut ::std::os::raw::c_void)>,
+    pub init_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, desc: *const sg_pipeline_desc, user_data: *mut ::std::os::raw::c_void)>,
+    pub init_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, desc: *const sg_pass_desc, user_data: *mut ::std::os::raw::c_void)>,
+    pub uninit_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub uninit_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub uninit_shader: ::std::option::Option<unsafe extern "C" fn(shd: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub uninit_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub uninit_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub fail_buffer: ::std::option::Option<unsafe extern "C" fn(buf: sg_buffer, user_data: *mut ::std::os::raw::c_void)>,
+    pub fail_image: ::std::option::Option<unsafe extern "C" fn(img: sg_image, user_data: *mut ::std::os::raw::c_void)>,
+    pub fail_shader: ::std::option::Option<unsafe extern "C" fn(shd: sg_shader, user_data: *mut ::std::os::raw::c_void)>,
+    pub fail_pipeline: ::std::option::Option<unsafe extern "C" fn(pip: sg_pipeline, user_data: *mut ::std::os::raw::c_void)>,
+    pub fail_pass: ::std::option::Option<unsafe extern "C" fn(pass: sg_pass, user_data: *mut ::std::os::raw::c_void)>,
+    pub push_debug_group: ::std::option::Option<unsafe extern "C" fn(name: *const ::std::os::raw::c_char, user_data: *mut ::std::os::raw::c_void)>,
+    pub pop_debug_group: ::std::option::Option<unsafe extern "C" fn(user_data: *mut ::std::os::raw::c_void)>,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_slot_info {
+    pub state: sg_resource_state,
+    pub res_id: u32,
+    pub ctx_id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_buffer_info {
+    pub slot: sg_slot_info,
+    pub update_frame_index: u32,
+    pub append_frame_index: u32,
+    pub append_pos: ::std::os::raw::c_int,
+    pub append_overflow: bool,
+    pub num_slots: ::std::os::raw::c_int,
+    pub active_slot: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_image_info {
+    pub slot: sg_slot_info,
+    pub upd_frame_index: u32,
+    pub num_slots: ::std::os::raw::c_int,
+    pub active_slot: ::std::os::raw::c_int,
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_shader_info {
+    pub slot: sg_slot_info,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pipeline_info {
+    pub slot: sg_slot_info,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_pass_info {
+    pub slot: sg_slot_info,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_gl_context_desc {
+    pub force_gles2: bool,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_metal_context_desc {
+    pub device: *const ::std::os::raw::c_void,
+    pub renderpass_descriptor_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub renderpass_descriptor_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub drawable_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub drawable_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_d3d11_context_desc {
+    pub device: *const ::std::os::raw::c_void,
+    pub device_context: *const ::std::os::raw::c_void,
+    pub render_target_view_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub render_target_view_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub depth_stencil_view_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub depth_stencil_view_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_wgpu_context_desc {
+    pub device: *const ::std::os::raw::c_void,
+    pub render_view_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub render_view_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub resolve_view_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub resolve_view_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub depth_stencil_view_cb: ::std::option::Option<unsafe extern "C" fn() -> *const ::std::os::raw::c_void>,
+    pub depth_stencil_view_userdata_cb: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *const ::std::os::raw::c_void>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_context_desc {
+    pub color_format: sg_pixel_format,
+    pub depth_format: sg_pixel_format,
+    pub sample_count: ::std::os::raw::c_int,
+    pub gl: sg_gl_context_desc,
+    pub metal: sg_metal_context_desc,
+    pub d3d11: sg_d3d11_context_desc,
+    pub wgpu: sg_wgpu_context_desc,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_commit_listener {
+    pub func: ::std::option::Option<unsafe extern "C" fn(user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_allocator {
+    pub alloc: ::std::option::Option<unsafe extern "C" fn(size: usize, user_data: *mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void>,
+    pub free: ::std::option::Option<unsafe extern "C" fn(ptr: *mut ::std::os::raw::c_void, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_logger {
+    pub func: ::std::option::Option<unsafe extern "C" fn(tag: *const ::std::os::raw::c_char, log_level: u32, log_item_id: u32, message_or_null: *const ::std::os::raw::c_char, line_nr: u32, filename_or_null: *const ::std::os::raw::c_char, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sg_desc {
+    pub _start_canary: u32,
+    pub buffer_pool_size: ::std::os::raw::c_int,
+    pub image_pool_size: ::std::os::raw::c_int,
+    pub shader_pool_size: ::std::os::raw::c_int,
+    pub pipeline_pool_size: ::std::os::raw::c_int,
+    pub pass_pool_size: ::std::os::raw::c_int,
+    pub context_pool_size: ::std::os::raw::c_int,
+    pub uniform_buffer_size: ::std::os::raw::c_int,
+    pub staging_buffer_size: ::std::os::raw::c_int,
+    pub sampler_cache_size: ::std::os::raw::c_int,
+    pub max_commit_listeners: ::std::os::raw::c_int,
+    pub disable_validation: bool,
+    pub allocator: sg_allocator,
+    pub logger: sg_logger,
+    pub context: sg_context_desc,
+    pub _end_canary: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct simgui_allocator_t {
+    pub alloc: ::std::option::Option<unsafe extern "C" fn(size: usize, user_data: *mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void>,
+    pub free: ::std::option::Option<unsafe extern "C" fn(ptr: *mut ::std::os::raw::c_void, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct simgui_logger_t {
+    pub func: ::std::option::Option<unsafe extern "C" fn(tag: *const ::std::os::raw::c_char, log_level: u32, log_item_id: u32, message_or_null: *const ::std::os::raw::c_char, line_nr: u32, filename_or_null: *const ::std::os::raw::c_char, user_data: *mut ::std::os::raw::c_void)>,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct simgui_desc_t {
+    pub max_vertices: ::std::os::raw::c_int,
+    pub color_format: sg_pixel_format,
+    pub depth_format: sg_pixel_format,
+    pub sample_count: ::std::os::raw::c_int,
+    pub ini_filename: *const ::std::os::raw::c_char,
+    pub no_default_font: bool,
+    pub disable_paste_override: bool,
+    pub disable_set_mouse_cursor: bool,
+    pub disable_windows_resize_from_edges: bool,
+    pub write_alpha_channel: bool,
+    pub allocator: simgui_allocator_t,
+    pub logger: simgui_logger_t,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct simgui_frame_desc_t {
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+    pub delta_time: f64,
+    pub dpi_scale: f32,
+}
@@ -0,0 +1,249 @@
+//! sokol::alloc - memory allocation hooks for the sokol libraries
+//!
+//! By default, the sokol libraries use `malloc()` and `free()`. With `salloc_setup()`,
+//! their internal allocations can be routed through a Rust allocator instead, and
+//! optionally be tracked per library.
+//!
+//! `salloc_setup()` must be called before `sapp_run()`, `sg_setup()` or
+//! `saudio_setup()`. Each library picks up the configuration at setup time.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::fmt;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::RwLock;
+
+pub mod ffi {
+    use std::os::raw::c_void;
+    use std::ptr::null;
+    use std::ptr::null_mut;
+
+    use super::SAllocCounters;
+
+    /// Stored in front of each allocation, so that `free()` knows how to release it.
+    #[repr(C)]
+    struct SAllocHeader {
+        size: usize,
+        allocator: &'static (dyn super::GlobalAlloc + Sync),
+        counters: Option<&'static SAllocCounters>,
+    }
+
+    const HEADER_SIZE: usize = 32;
+    const ALIGNMENT: usize = 16;
+
+    const _: () = assert!(std::mem::size_of::<SAllocHeader>() <= HEADER_SIZE);
+
+    /// Returns the `user_data` to pass along with `salloc_alloc()` and `salloc_free()`,
+    /// or `None` if the library should use its default allocator.
+    pub fn salloc_user_data(module: super::SAllocModule) -> Option<*const c_void> {
+        let config = super::CONFIG.read().unwrap_or_else(|e| e.into_inner());
+
+        match (config.allocator, config.tracking) {
+            (super::SAllocator::Default, false) => None,
+            (_, false) => Some(null()),
+            (_, true) => Some(super::counters(module) as *const SAllocCounters as *const c_void),
+        }
+    }
+
+    pub extern fn salloc_alloc(size: usize, user_data: *mut c_void) -> *mut c_void {
+        let allocator = super::CONFIG.read().unwrap_or_else(|e| e.into_inner()).allocator.get();
+
+        let layout = match super::Layout::from_size_align(HEADER_SIZE + size, ALIGNMENT) {
+            Ok(layout) => layout,
+            Err(_) => return null_mut(),
+        };
+
+        let counters = if user_data.is_null() {
+            None
+        } else {
+            Some(unsafe { &*(user_data as *const SAllocCounters) })
+        };
+
+        unsafe {
+            let ptr = allocator.alloc(layout);
+            if ptr.is_null() {
+                return null_mut();
+            }
+
+            (ptr as *mut SAllocHeader).write(SAllocHeader {
+                size,
+                allocator,
+                counters,
+            });
+
+            if let Some(counters) = counters {
+                counters.on_alloc(size);
+            }
+
+            ptr.add(HEADER_SIZE) as *mut c_void
+        }
+    }
+
+    pub extern fn salloc_free(ptr: *mut c_void, _user_data: *mut c_void) {
+        if ptr.is_null() {
+            return;
+        }
+
+        unsafe {
+            let base = (ptr as *mut u8).sub(HEADER_SIZE);
+            let header = (base as *const SAllocHeader).read();
+
+            if let Some(counters) = header.counters {
+                counters.on_free(header.size);
+            }
+
+            let layout = super::Layout::from_size_align_unchecked(HEADER_SIZE + header.size, ALIGNMENT);
+            header.allocator.dealloc(base, layout);
+        }
+    }
+}
+
+/// The sokol libraries which can be configured with a custom allocator.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SAllocModule {
+    App,
+    Gfx,
+    Audio,
+}
+
+#[derive(Copy, Clone)]
+pub enum SAllocator {
+    /// sokol's own `malloc()` and `free()`.
+    Default,
+    /// The Rust global allocator, i.e. the `#[global_allocator]` if one is set.
+    Global,
+    /// A user-provided allocator.
+    Custom(&'static (dyn GlobalAlloc + Sync)),
+}
+
+impl SAllocator {
+    fn get(self) -> &'static (dyn GlobalAlloc + Sync) {
+        match self {
+            SAllocator::Default => &std::alloc::System,
+            SAllocator::Global => &RustGlobal,
+            SAllocator::Custom(allocator) => allocator,
+        }
+    }
+}
+
+impl Default for SAllocator {
+    fn default() -> Self {
+        SAllocator::Default
+    }
+}
+
+impl fmt::Debug for SAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SAllocator::Default => f.write_str("Default"),
+            SAllocator::Global => f.write_str("Global"),
+            SAllocator::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct SAllocDesc {
+    pub allocator: SAllocator,
+    /// Count allocations per library, see `salloc_query_stats()`.
+    /// With `SAllocator::Default`, this uses the system allocator.
+    pub tracking: bool,
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SAllocStats {
+    /// Number of bytes currently allocated.
+    pub live_bytes: usize,
+    /// Number of allocations not yet freed.
+    pub live_allocations: usize,
+    /// Number of allocations since program start.
+    pub total_allocations: usize,
+}
+
+struct RustGlobal;
+
+unsafe impl GlobalAlloc for RustGlobal {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        std::alloc::alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        std::alloc::dealloc(ptr, layout)
+    }
+}
+
+struct SAllocConfig {
+    allocator: SAllocator,
+    tracking: bool,
+}
+
+static CONFIG: RwLock<SAllocConfig> = RwLock::new(SAllocConfig {
+    allocator: SAllocator::Default,
+    tracking: false,
+});
+
+struct SAllocCounters {
+    live_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    total_allocations: AtomicUsize,
+}
+
+impl SAllocCounters {
+    const fn new() -> Self {
+        SAllocCounters {
+            live_bytes: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+            total_allocations: AtomicUsize::new(0),
+        }
+    }
+
+    fn on_alloc(&self, size: usize) {
+        self.live_bytes.fetch_add(size, Ordering::Relaxed);
+        self.live_allocations.fetch_add(1, Ordering::Relaxed);
+        self.total_allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_free(&self, size: usize) {
+        self.live_bytes.fetch_sub(size, Ordering::Relaxed);
+        self.live_allocations.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> SAllocStats {
+        SAllocStats {
+            live_bytes: self.live_bytes.load(Ordering::Relaxed),
+            live_allocations: self.live_allocations.load(Ordering::Relaxed),
+            total_allocations: self.total_allocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static APP_COUNTERS: SAllocCounters = SAllocCounters::new();
+static GFX_COUNTERS: SAllocCounters = SAllocCounters::new();
+static AUDIO_COUNTERS: SAllocCounters = SAllocCounters::new();
+
+fn counters(module: SAllocModule) -> &'static SAllocCounters {
+    match module {
+        SAllocModule::App => &APP_COUNTERS,
+        SAllocModule::Gfx => &GFX_COUNTERS,
+        SAllocModule::Audio => &AUDIO_COUNTERS,
+    }
+}
+
+/// Configures memory allocation for the sokol libraries.
+///
+/// Whether a library calls into Rust for its allocations, and whether these are
+/// tracked, is decided when the library is set up. The allocator can be changed at
+/// any time: each allocation is released through the allocator it was made with.
+pub fn salloc_setup(desc: SAllocDesc) {
+    let mut config = CONFIG.write().unwrap_or_else(|e| e.into_inner());
+    config.allocator = desc.allocator;
+    config.tracking = desc.tracking;
+}
+
+/// Returns the allocation statistics of a sokol library.
+///
+/// Only allocations made while tracking was enabled are counted.
+pub fn salloc_query_stats(module: SAllocModule) -> SAllocStats {
+    counters(module).stats()
+}
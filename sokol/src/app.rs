@@ -31,6 +31,8 @@ pub mod ffi {
         mouse_button: super::SAppMouseButton,
         mouse_x: f32,
         mouse_y: f32,
+        mouse_dx: f32,
+        mouse_dy: f32,
         scroll_x: f32,
         scroll_y: f32,
         num_touches: c_int,
@@ -53,6 +55,8 @@ pub mod ffi {
                 mouse_button: event.mouse_button,
                 mouse_x: event.mouse_x,
                 mouse_y: event.mouse_y,
+                mouse_dx: event.mouse_dx,
+                mouse_dy: event.mouse_dy,
                 scroll_x: event.scroll_x,
                 scroll_y: event.scroll_y,
                 num_touches: event.num_touches,
@@ -65,39 +69,97 @@ pub mod ffi {
         }
     }
 
+    /// Owns the C strings referenced by a description passed to one of the sokol libraries.
+    ///
+    /// The description must not be used after this has been dropped, so it must
+    /// outlive the call which consumes the description.
+    #[derive(Default)]
+    pub struct SAppStrings {
+        strings: Vec<CString>,
+    }
+
+    impl SAppStrings {
+        pub fn add(&mut self, s: &str) -> *const c_char {
+            let s = CString::new(s).unwrap();
+            // the heap buffer doesn't move when the CString is moved into the Vec
+            let ptr = s.as_ptr();
+            self.strings.push(s);
+            ptr
+        }
+
+        pub fn add_opt(&mut self, s: Option<&str>) -> *const c_char {
+            match s {
+                Some(s) => self.add(s),
+                None => null(),
+            }
+        }
+
+        /// Same as `add()`, but an empty string becomes NULL, so sokol fills in its default.
+        pub fn add_or_default(&mut self, s: &str) -> *const c_char {
+            if s.is_empty() {
+                null()
+            } else {
+                self.add(s)
+            }
+        }
+    }
+
+    pub type SAppLoggerFn = extern fn(*const c_char, u32, u32, *const c_char, u32, *const c_char, *mut c_void);
+
     #[repr(C)]
     #[derive(Debug)]
     pub struct SAppLogger {
-      func: unsafe extern fn(),
+      func: Option<SAppLoggerFn>,
       user_data: *const c_void,
     }
+
+    /// Routes all log messages to `sokol::log`.
     impl Default for SAppLogger {
       fn default() -> Self {
         SAppLogger {
-          func: slog_func,
+          func: Some(crate::log::ffi::slog_rust_func),
           user_data: null(),
         }
       }
     }
     
+    pub type SAppAllocFn = extern fn(usize, *mut c_void) -> *mut c_void;
+    pub type SAppFreeFn = extern fn(*mut c_void, *mut c_void);
+
     #[repr(C)]
     #[derive(Debug)]
     pub struct SAppAllocator {
-      alloc: *const c_void,
-      free: *const c_void,
+      alloc: Option<SAppAllocFn>,
+      free: Option<SAppFreeFn>,
       user_data: *const c_void,
     }
 
     impl Default for SAppAllocator {
       fn default() -> Self {
         SAppAllocator {
-          alloc: null(),
-          free: null(),
+          alloc: None,
+          free: None,
           user_data: null(),
         }
       }
     }
 
+    impl SAppAllocator {
+      /// Allocator for one of the sokol libraries, as configured with `salloc_setup()`.
+      pub fn make(module: crate::alloc::SAllocModule) -> Self {
+        use crate::alloc::ffi::*;
+
+        match salloc_user_data(module) {
+          None => Default::default(),
+          Some(user_data) => SAppAllocator {
+            alloc: Some(salloc_alloc),
+            free: Some(salloc_free),
+            user_data,
+          },
+        }
+      }
+    }
+
     #[repr(C)]
     #[derive(Debug)]
     pub struct SAppDesc {
@@ -143,9 +205,120 @@ pub mod ffi {
         ios_keyboard_resizes_canvas: bool,
     }
 
-    extern {
-        fn slog_func();
+    mod layout {
+        use sys::raw;
+
+        use super::*;
+        use super::super::{SAppEventType, SAppIconDesc, SAppImageDesc, SAppKeycode};
+        use super::super::{SAppModifier, SAppMouseButton, SAppRange, SAppTouchPoint};
+
+        assert_layout!(SAppEvent, raw::sapp_event, {
+            frame_count => frame_count,
+            event_type => type_,
+            key_code => key_code,
+            char_code => char_code,
+            key_repeat => key_repeat,
+            modifiers => modifiers,
+            mouse_button => mouse_button,
+            mouse_x => mouse_x,
+            mouse_y => mouse_y,
+            mouse_dx => mouse_dx,
+            mouse_dy => mouse_dy,
+            scroll_x => scroll_x,
+            scroll_y => scroll_y,
+            num_touches => num_touches,
+            touches => touches,
+            window_width => window_width,
+            window_height => window_height,
+            framebuffer_width => framebuffer_width,
+            framebuffer_height => framebuffer_height,
+        });
 
+        assert_layout!(SAppLogger, raw::sapp_logger, {
+            func => func,
+            user_data => user_data,
+        });
+
+        assert_layout!(SAppAllocator, raw::sapp_allocator, {
+            alloc => alloc,
+            free => free,
+            user_data => user_data,
+        });
+
+        assert_layout!(SAppDesc, raw::sapp_desc, {
+            init_cb => init_cb,
+            frame_cb => frame_cb,
+            cleanup_cb => cleanup_cb,
+            event_cb => event_cb,
+            user_data => user_data,
+            init_userdata_cb => init_userdata_cb,
+            frame_userdata_cb => frame_userdata_cb,
+            cleanup_userdata_cb => cleanup_userdata_cb,
+            event_userdata_cb => event_userdata_cb,
+            width => width,
+            height => height,
+            sample_count => sample_count,
+            swap_interval => swap_interval,
+            high_dpi => high_dpi,
+            fullscreen => fullscreen,
+            alpha => alpha,
+            window_title => window_title,
+            enable_clipboard => enable_clipboard,
+            clipboard_size => clipboard_size,
+            enable_dragndrop => enable_dragndrop,
+            max_dropped_files => max_dropped_files,
+            max_dropped_file_path_length => max_dropped_file_path_length,
+            icon => icon,
+            allocator => allocator,
+            logger => logger,
+            gl_force_gles2 => gl_force_gles2,
+            gl_major_version => gl_major_version,
+            gl_minor_version => gl_minor_version,
+            win32_console_utf8 => win32_console_utf8,
+            win32_console_create => win32_console_create,
+            win32_console_attach => win32_console_attach,
+            html5_canvas_name => html5_canvas_name,
+            html5_canvas_resize => html5_canvas_resize,
+            html5_preserve_drawing_buffer => html5_preserve_drawing_buffer,
+            html5_premultiplied_alpha => html5_premultiplied_alpha,
+            html5_ask_leave_site => html5_ask_leave_site,
+            ios_keyboard_resizes_canvas => ios_keyboard_resizes_canvas,
+        });
+
+        assert_layout!(SAppTouchPoint, raw::sapp_touchpoint, {
+            identifier => identifier,
+            pos_x => pos_x,
+            pos_y => pos_y,
+            changed => changed,
+        });
+
+        assert_layout!(SAppRange, raw::sapp_range, {
+            ptr => ptr,
+            size => size,
+        });
+
+        assert_layout!(SAppImageDesc, raw::sapp_image_desc, {
+            width => width,
+            height => height,
+            pixels => pixels,
+        });
+
+        assert_layout!(SAppIconDesc, raw::sapp_icon_desc, {
+            sokol_default => sokol_default,
+            images => images,
+        });
+
+        assert_layout!(SAppEventType, raw::sapp_event_type);
+        assert_layout!(SAppKeycode, raw::sapp_keycode);
+        assert_layout!(SAppMouseButton, raw::sapp_mousebutton);
+        assert_layout!(SAppModifier, u32);
+
+        const _: () = assert!(SAPP_MAX_TOUCHPOINTS == raw::SAPP_MAX_TOUCHPOINTS as usize);
+        const _: () = assert!(SAPP_MAX_MOUSEBUTTONS == raw::SAPP_MAX_MOUSEBUTTONS as usize);
+        const _: () = assert!(SAPP_MAX_ICONIMAGES == raw::SAPP_MAX_ICONIMAGES as usize);
+    }
+
+    extern {
         /// sokol entry point (compiled with SOKOL_NO_ENTRY)
         pub fn sapp_run(desc: *const SAppDesc) -> c_int;
 
@@ -178,13 +351,10 @@ pub mod ffi {
         pub fn sapp_win32_get_hwnd() -> *const c_void;
     }
 
-    pub fn sapp_make_desc(app: &super::SAppImpl) -> SAppDesc {
+    pub fn sapp_make_desc(app: &super::SAppImpl, strings: &mut SAppStrings) -> SAppDesc {
         let app_ptr = app as *const super::SAppImpl;
         let desc = &app.desc;
 
-        let window_title = CString::new(&*desc.window_title).unwrap();
-        let canvas_name = CString::new(&*desc.html5_canvas_name).unwrap();
-
         SAppDesc {
             init_cb: null(),
             frame_cb: null(),
@@ -204,14 +374,14 @@ pub mod ffi {
             high_dpi: desc.high_dpi,
             fullscreen: desc.fullscreen,
             alpha: desc.alpha,
-            window_title: window_title.into_raw(),
+            window_title: strings.add(&desc.window_title),
             enable_clipboard: desc.enable_clipboard,
             clipboard_size: desc.clipboard_size,
             enable_dragndrop: desc.enable_dragndrop,
             max_dropped_files: desc.max_dropped_files,
             max_dropped_file_path_length: desc.max_dropped_file_path_length,
             icon: desc.icon,
-            allocator: Default::default(),
+            allocator: SAppAllocator::make(crate::alloc::SAllocModule::App),
             logger: Default::default(),
 
             gl_force_gles2: desc.gl_force_gles2,
@@ -220,7 +390,7 @@ pub mod ffi {
             win32_console_utf8: desc.win32_console_utf8,
             win32_console_create: desc.win32_console_create,
             win32_console_attach: desc.win32_console_attach,
-            html5_canvas_name: canvas_name.into_raw(),
+            html5_canvas_name: strings.add(&desc.html5_canvas_name),
             html5_canvas_resize: desc.html5_canvas_resize,
             html5_preserve_drawing_buffer: desc.html5_preserve_drawing_buffer,
             html5_premultiplied_alpha: desc.html5_premultiplied_alpha,
@@ -260,6 +430,8 @@ pub mod ffi {
             mouse_button: e.mouse_button,
             mouse_x: e.mouse_x,
             mouse_y: e.mouse_y,
+            mouse_dx: e.mouse_dx,
+            mouse_dy: e.mouse_dy,
             scroll_x: e.scroll_x,
             scroll_y: e.scroll_y,
             num_touches: e.num_touches,
@@ -315,6 +487,8 @@ pub enum SAppEventType {
     Resumed,
     UpdateCursor,
     QuitRequested,
+    ClipboardPasted,
+    FilesDropped,
 }
 
 #[repr(C)]
@@ -446,10 +620,10 @@ pub enum SAppKeycode {
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SAppMouseButton {
-    Invalid = -1,
-    Left = 0,
-    Right = 1,
-    Middle = 2,
+    Left = 0x0,
+    Right = 0x1,
+    Middle = 0x2,
+    Invalid = 0x100,
 }
 
 bitflags! {
@@ -459,6 +633,9 @@ bitflags! {
         const CONTROL = 0x02;
         const ALT = 0x04;
         const SUPER = 0x08;
+        const LMB = 0x100;
+        const RMB = 0x200;
+        const MMB = 0x400;
     }
 }
 
@@ -482,6 +659,8 @@ pub struct SAppEvent {
     pub mouse_button: SAppMouseButton,
     pub mouse_x: f32,
     pub mouse_y: f32,
+    pub mouse_dx: f32,
+    pub mouse_dy: f32,
     pub scroll_x: f32,
     pub scroll_y: f32,
     pub num_touches: i32,
@@ -643,9 +822,10 @@ impl SAppImpl {
 pub fn sapp_run<S: SApp + 'static>(callbacks: S,
                                    desc: SAppDesc) -> i32 {
     let app = SAppImpl::new(callbacks, desc);
+    let mut strings = ffi::SAppStrings::default();
 
     unsafe {
-        ffi::sapp_run(&ffi::sapp_make_desc(&app))
+        ffi::sapp_run(&ffi::sapp_make_desc(&app, &mut strings))
     }
 }
 
@@ -24,6 +24,25 @@ pub mod ffi {
         logger: SAppLogger,
     }
 
+    mod layout {
+        use sys::raw;
+
+        use super::*;
+
+        assert_layout!(SAudioDesc, raw::saudio_desc, {
+            sample_rate => sample_rate,
+            num_channels => num_channels,
+            buffer_frames => buffer_frames,
+            packet_frames => packet_frames,
+            num_packets => num_packets,
+            stream_cb => stream_cb,
+            stream_userdata_cb => stream_userdata_cb,
+            user_data => user_data,
+            allocator => allocator,
+            logger => logger,
+        });
+    }
+
     extern {
         pub fn saudio_setup(desc: *const SAudioDesc);
         pub fn saudio_shutdown();
@@ -54,7 +73,7 @@ pub mod ffi {
                 None
             },
             user_data: app_ptr,
-            allocator: Default::default(),
+            allocator: SAppAllocator::make(crate::alloc::SAllocModule::Audio),
             logger: Default::default(),
         }
     }
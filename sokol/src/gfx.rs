@@ -7,9 +7,37 @@ use std::fmt;
 use std::os::raw::c_void;
 use std::os::raw::c_int;
 
+use bytemuck::Pod;
+
+use crate::app::ffi::SAppStrings;
+
+mod context;
+mod encoder;
+mod image;
+mod owned;
+#[cfg(feature = "naga")]
+mod shader;
+mod stats;
+mod trace;
+mod uniforms;
+mod validate;
+mod vertex;
+
+pub use self::context::*;
+pub use self::encoder::*;
+pub use self::image::*;
+pub use self::owned::*;
+#[cfg(feature = "naga")]
+pub use self::shader::*;
+pub use self::stats::*;
+pub use self::trace::*;
+pub use self::uniforms::*;
+pub use self::validate::*;
+pub use self::vertex::*;
+
 mod ffi {
     use std::borrow::Borrow;
-    use std::ffi::CString;
+    use std::ffi::CStr;
     use std::fmt;
     use std::os::raw::c_char;
     use std::os::raw::c_int;
@@ -21,18 +49,18 @@ mod ffi {
     use crate::app::ffi::*;
 
     use super::SG_CUBEFACE_NUM;
+    use super::SG_MAX_MIPMAPS;
+    use super::SG_NUM_INFLIGHT_FRAMES;
     use super::SgFaceWinding;
 
     const _SG_INVALID_ID: usize = 0;
     const _SG_NUM_SHADER_STAGES: usize = 2;
-    const SG_NUM_INFLIGHT_FRAMES: usize = 2;
-    const SG_MAX_COLOR_ATTACHMENTS: usize = 4;
-    const SG_MAX_SHADERSTAGE_BUFFERS: usize = 8;
-    const SG_MAX_SHADERSTAGE_IMAGES: usize = 12;
-    const SG_MAX_SHADERSTAGE_UBS: usize = 4;
-    const SG_MAX_UB_MEMBERS: usize = 16;
-    const SG_MAX_VERTEX_ATTRIBUTES: usize = 16;
-    const SG_MAX_MIPMAPS: usize = 16;
+    pub(super) const SG_MAX_COLOR_ATTACHMENTS: usize = 4;
+    pub(super) const SG_MAX_SHADERSTAGE_BUFFERS: usize = 8;
+    pub(super) const SG_MAX_SHADERSTAGE_IMAGES: usize = 12;
+    pub(super) const SG_MAX_SHADERSTAGE_UBS: usize = 4;
+    pub(super) const SG_MAX_UB_MEMBERS: usize = 16;
+    pub(super) const SG_MAX_VERTEX_ATTRIBUTES: usize = 16;
     const _SG_MAX_TEXTUREARRAY_LAYERS: usize = 128;
 
     #[repr(C)]
@@ -109,86 +137,83 @@ mod ffi {
         }
     }
 
-    #[repr(C)]
-    #[derive(Debug)]
-    struct SgGLContextDesc {
-        force_gles2: bool,
-    }
-
-    #[repr(C)]
-    #[derive(Debug)]
-    struct SgMetalContextDesc {
-        device: *const c_void,
-        renderpass_descriptor_cb: *const c_void,
-        renderpass_descriptor_userdata_cb: *const c_void,
-        drawable_cb: *const c_void,
-        drawable_userdata_cb: *const c_void,
-        user_data: *const c_void,
-    }
-
-    #[repr(C)]
-    #[derive(Debug)]
-    struct SgD3D11ContextDesc {
-        device: *const c_void,
-        device_context: *const c_void,
-        render_target_view_cb: *const c_void,
-        render_target_view_userdata_cb: *const c_void,
-        depth_stencil_view_cb: *const c_void,
-        depth_stencil_view_userdata_cb: *const c_void,
-        user_data: *const c_void,
-    }
-
-    #[repr(C)]
-    #[derive(Debug)]
-    struct SgWGPUContextDesc {
-        device: *const c_void,
-        render_view_cb: *const c_void,
-        render_view_userdata_cb: *const c_void,
-        resolve_view_cb: *const c_void,
-        resolve_view_userdata_cb: *const c_void,
-        depth_stencil_view_cb: *const c_void,
-        depth_stencil_view_userdata_cb: *const c_void,
-        user_data: *const c_void,
-    }
-
-    #[repr(C)]
-    #[derive(Debug)]
-    struct SgContextDesc {
-        color_format: super::SgPixelFormat,
-        depth_format: super::SgPixelFormat,
-        sample_count: c_int,
-        gl: SgGLContextDesc,
-        metal: SgMetalContextDesc,
-        d3d11: SgD3D11ContextDesc,
-        wgpu: SgWGPUContextDesc,
-    }
-
     #[repr(C)]
     #[derive(Debug)]
     pub struct SgDesc {
         _start_canary: u32,
-        pub desc: super::SgDesc,
+        buffer_pool_size: c_int,
+        image_pool_size: c_int,
+        shader_pool_size: c_int,
+        pipeline_pool_size: c_int,
+        pass_pool_size: c_int,
+        context_pool_size: c_int,
+        uniform_buffer_size: c_int,
+        staging_buffer_size: c_int,
+        sampler_cache_size: c_int,
+        max_commit_listeners: c_int,
+        disable_validation: bool,
         allocator: SAppAllocator,
         logger: SAppLogger,
-        context: SgContextDesc,
+        context: super::SgContextDesc,
         _end_canary: u32,
     }
 
     impl SgDesc {
+        /// Uses `desc.context`, or the render context of sokol_app if there is none.
         pub fn make(desc: &super::SgDesc) -> SgDesc {
-            unsafe {
-                SgDesc {
-                    _start_canary: 0,
-                    desc: *desc,
-                    allocator: Default::default(),
-                    logger: Default::default(),
-                    context: sapp_sgcontext(),
-                    _end_canary: 0,
-                }
+            let context = desc.context.unwrap_or_else(default_context);
+
+            SgDesc {
+                _start_canary: 0,
+                buffer_pool_size: desc.buffer_pool_size,
+                image_pool_size: desc.image_pool_size,
+                shader_pool_size: desc.shader_pool_size,
+                pipeline_pool_size: desc.pipeline_pool_size,
+                pass_pool_size: desc.pass_pool_size,
+                context_pool_size: desc.context_pool_size,
+                uniform_buffer_size: desc.uniform_buffer_size,
+                staging_buffer_size: desc.staging_buffer_size,
+                sampler_cache_size: desc.sampler_cache_size,
+                max_commit_listeners: desc.max_commit_listeners,
+                disable_validation: desc.disable_validation,
+                allocator: SAppAllocator::make(crate::alloc::SAllocModule::Gfx),
+                logger: Default::default(),
+                context,
+                _end_canary: 0,
+            }
+        }
+
+        pub fn desc(&self) -> super::SgDesc {
+            super::SgDesc {
+                buffer_pool_size: self.buffer_pool_size,
+                image_pool_size: self.image_pool_size,
+                shader_pool_size: self.shader_pool_size,
+                pipeline_pool_size: self.pipeline_pool_size,
+                pass_pool_size: self.pass_pool_size,
+                context_pool_size: self.context_pool_size,
+                uniform_buffer_size: self.uniform_buffer_size,
+                staging_buffer_size: self.staging_buffer_size,
+                sampler_cache_size: self.sampler_cache_size,
+                max_commit_listeners: self.max_commit_listeners,
+                disable_validation: self.disable_validation,
+                context: Some(self.context),
             }
         }
     }
 
+    #[cfg(not(feature = "dummy"))]
+    fn default_context() -> super::SgContextDesc {
+        unsafe {
+            sapp_sgcontext()
+        }
+    }
+
+    /// The dummy renderer is built without sokol_app, and doesn't need a render context.
+    #[cfg(feature = "dummy")]
+    fn default_context() -> super::SgContextDesc {
+        Default::default()
+    }
+
     #[repr(C)]
     #[derive(Copy, Clone, Debug)]
     pub struct SgRange {
@@ -204,6 +229,15 @@ mod ffi {
             }
         }
     }
+
+    impl SgRange {
+        pub fn from_slice(data: &[u8]) -> Self {
+            SgRange {
+                ptr: data.as_ptr() as *const c_void,
+                size: data.len() as c_size_t,
+            }
+        }
+    }
         
 
     #[repr(C)]
@@ -223,7 +257,7 @@ mod ffi {
     }
 
     impl SgBufferDesc {
-        pub fn make<T>(content: Option<&T>, desc: &super::SgBufferDesc) -> SgBufferDesc {
+        pub fn make<T>(content: Option<&T>, desc: &super::SgBufferDesc, strings: &mut SAppStrings) -> SgBufferDesc {
             let ptr = if content.is_some() {
                 content.unwrap() as *const T
             } else {
@@ -231,18 +265,45 @@ mod ffi {
             };
             let ptrv = ptr as *const c_void;
             
-            SgBufferDesc {
+            let mut buf = SgBufferDesc {
                 _start_canary: 0,
                 size: desc.size as c_size_t,
                 buffer_type: desc.buffer_type,
                 usage: desc.usage,
                 data: SgRange { ptr: ptrv, size: desc.size as c_size_t },
-                label: null(),
+                label: strings.add_opt(desc.label),
                 gl_buffers: [0, 0],
                 mtl_buffers: [null(), null()],
                 d3d11_buffer: null(),
                 wgpu_buffer: null(),
                 _end_canary: 0,
+            };
+
+            if let Some(native) = desc.native {
+                match native {
+                    super::SgNativeBuffer::GL(buffers) => buf.gl_buffers = buffers,
+                    super::SgNativeBuffer::Metal(buffers) => buf.mtl_buffers = buffers,
+                    super::SgNativeBuffer::D3D11(buffer) => buf.d3d11_buffer = buffer,
+                    super::SgNativeBuffer::WGPU(buffer) => buf.wgpu_buffer = buffer,
+                }
+            }
+
+            buf
+        }
+
+        /// Sets the initial content, and the size of the buffer to match it.
+        pub fn set_data(&mut self, data: &[u8]) {
+            self.size = data.len() as c_size_t;
+            self.data = SgRange::from_slice(data);
+        }
+
+        pub fn to_desc(&self) -> super::SgBufferDesc<'static> {
+            super::SgBufferDesc {
+                size: self.size as usize,
+                buffer_type: self.buffer_type,
+                usage: self.usage,
+                label: None,
+                native: None,
             }
         }
     }
@@ -266,26 +327,20 @@ mod ffi {
     }
 
     impl SgImageData {
-        pub fn make<T>(content: Option<Vec<Vec<(*const T, i32)>>>) -> SgImageData {
+        pub fn make(content: Option<&super::SgImageContent>) -> SgImageData {
             let mut cnt = SgImageData {
                 ..Default::default()
             };
 
-            match content {
-                None => {}
-                Some(content) => {
-                    for (x, v) in content.iter().enumerate() {
-                        for (y, (data, size)) in v.iter().enumerate() {
-                            let ptr = *data as *const T;
-
-                            cnt.subimage[x][y] = SgRange {
-                                ptr: ptr as *const c_void,
-                                size: *size as usize,
-                            };
+            if let Some(content) = content {
+                for (face, subimages) in cnt.subimage.iter_mut().enumerate() {
+                    for (mip, subimage) in subimages.iter_mut().enumerate() {
+                        if let Some(data) = content.subimage(face, mip) {
+                            *subimage = SgRange::from_slice(data);
                         }
                     }
                 }
-            };
+            }
 
             cnt
         }
@@ -325,8 +380,10 @@ mod ffi {
     }
 
     impl SgImageDesc {
-        pub fn make<T>(content: Option<Vec<Vec<(*const T, i32)>>>, desc: &super::SgImageDesc) -> SgImageDesc {
-            SgImageDesc {
+        pub fn make(content: Option<&super::SgImageContent>,
+                    desc: &super::SgImageDesc,
+                    strings: &mut SAppStrings) -> SgImageDesc {
+            let mut img = SgImageDesc {
                 _start_canary: 0,
                 image_type: desc.image_type,
                 render_target: desc.render_target,
@@ -347,7 +404,7 @@ mod ffi {
                 min_lod: desc.min_lod,
                 max_lod: desc.max_lod,
                 data: SgImageData::make(content),
-                label: null(),
+                label: strings.add_opt(desc.label),
                 gl_textures: [0; SG_NUM_INFLIGHT_FRAMES],
                 gl_texture_target: 0,
                 mtl_textures: [null(); SG_NUM_INFLIGHT_FRAMES],
@@ -355,6 +412,48 @@ mod ffi {
                 d3d11_shader_resource_view: null(),
                 wgpu_texture: null(),
                 _end_canary: 0,
+            };
+
+            if let Some(native) = desc.native {
+                match native {
+                    super::SgNativeImage::GL { textures, target } => {
+                        img.gl_textures = textures;
+                        img.gl_texture_target = target;
+                    }
+                    super::SgNativeImage::Metal(textures) => img.mtl_textures = textures,
+                    super::SgNativeImage::D3D11 { texture, shader_resource_view } => {
+                        img.d3d11_texture = texture;
+                        img.d3d11_shader_resource_view = shader_resource_view;
+                    }
+                    super::SgNativeImage::WGPU(texture) => img.wgpu_texture = texture,
+                }
+            }
+
+            img
+        }
+
+        pub fn to_desc(&self) -> super::SgImageDesc<'static> {
+            super::SgImageDesc {
+                image_type: self.image_type,
+                render_target: self.render_target,
+                width: self.width,
+                height: self.height,
+                num_slices: self.num_slices,
+                num_mipmaps: self.num_mipmaps,
+                usage: self.usage,
+                pixel_format: self.pixel_format,
+                sample_count: self.sample_count,
+                min_filter: self.min_filter,
+                mag_filter: self.mag_filter,
+                wrap_u: self.wrap_u,
+                wrap_v: self.wrap_v,
+                wrap_w: self.wrap_w,
+                border_color: self.border_color,
+                max_anisotropy: self.max_anisotropy,
+                min_lod: self.min_lod,
+                max_lod: self.max_lod,
+                label: None,
+                native: None,
             }
         }
     }
@@ -461,16 +560,7 @@ mod ffi {
     }
 
     impl SgShaderDesc {
-        pub fn make(desc: &super::SgShaderDesc) -> SgShaderDesc {
-            let from_str = |s: Option<&str>| -> *const c_char {
-                if s.is_some() {
-                    let source = s.unwrap();
-                    CString::new(source).unwrap().into_raw()
-                } else {
-                    null()
-                }
-            };
-            
+        pub fn make(desc: &super::SgShaderDesc, strings: &mut SAppStrings) -> SgShaderDesc {
             let (vs_bytes, vs_size) = Self::collect_bytecode(desc.vs.byte_code);
             let (fs_bytes, fs_size) = Self::collect_bytecode(desc.fs.byte_code);
 
@@ -478,28 +568,28 @@ mod ffi {
                 _start_canary: 0,
                 attrs: Default::default(),
                 vs: SgShaderStageDesc {
-                    source: from_str(desc.vs.source),
+                    source: strings.add_opt(desc.vs.source),
                     bytecode: SgRange { ptr: vs_bytes as *const c_void, size: vs_size as c_size_t },
-                    entry: from_str(desc.vs.entry),
+                    entry: strings.add_opt(desc.vs.entry),
                     ..Default::default()
                 },
                 fs: SgShaderStageDesc {
-                    source: from_str(desc.fs.source),
+                    source: strings.add_opt(desc.fs.source),
                     bytecode: SgRange { ptr: fs_bytes as *const c_void, size: fs_size as c_size_t },
-                    entry: from_str(desc.fs.entry),
+                    entry: strings.add_opt(desc.fs.entry),
                     ..Default::default()
                 },
-                label: null(),
+                label: strings.add_opt(desc.label),
                 _end_canary: 0,
             };
 
-            Self::collect_attrs(&mut shd, &desc.attrs);
+            Self::collect_attrs(&mut shd, &desc.attrs, strings);
 
-            Self::collect_uniform_blocks(&mut shd.vs, &desc.vs.uniform_blocks);
-            Self::collect_images(&mut shd.vs, &desc.vs.images);
+            Self::collect_uniform_blocks(&mut shd.vs, &desc.vs.uniform_blocks, strings);
+            Self::collect_images(&mut shd.vs, &desc.vs.images, strings);
 
-            Self::collect_uniform_blocks(&mut shd.fs, &desc.fs.uniform_blocks);
-            Self::collect_images(&mut shd.fs, &desc.fs.images);
+            Self::collect_uniform_blocks(&mut shd.fs, &desc.fs.uniform_blocks, strings);
+            Self::collect_images(&mut shd.fs, &desc.fs.images, strings);
 
             shd
         }
@@ -515,52 +605,139 @@ mod ffi {
         }
 
         fn collect_attrs(desc: &mut SgShaderDesc,
-                         src: &[super::SgShaderAttrDesc]) {
+                         src: &[super::SgShaderAttrDesc],
+                         strings: &mut SAppStrings) {
             for (idx, attr) in src.iter().enumerate() {
-                let name = CString::new(attr.name).unwrap();
-                let sem_name = CString::new(attr.sem_name).unwrap();
-
                 desc.attrs[idx] = SgShaderAttrDesc {
-                    name: name.into_raw(),
-                    sem_name: sem_name.into_raw(),
+                    name: strings.add_or_default(attr.name),
+                    sem_name: strings.add_or_default(attr.sem_name),
                     sem_index: attr.sem_index,
                 };
             }
         }
 
         fn collect_uniforms(desc: &mut SgShaderUniformBlockDesc,
-                            src: &[super::SgShaderUniformDesc]) {
+                            src: &[super::SgShaderUniformDesc],
+                            strings: &mut SAppStrings) {
             for (idx, u) in src.iter().enumerate() {
                 let dst = &mut desc.uniforms[idx];
 
-                let name = CString::new(u.name).unwrap();
-
-                dst.name = name.into_raw();
+                dst.name = strings.add_or_default(u.name);
                 dst.uniform_type = u.uniform_type;
                 dst.array_count = u.array_count;
             }
         }
 
         fn collect_uniform_blocks(desc: &mut SgShaderStageDesc,
-                                  src: &[super::SgShaderUniformBlockDesc]) {
+                                  src: &[super::SgShaderUniformBlockDesc],
+                                  strings: &mut SAppStrings) {
             for (idx, ub) in src.iter().enumerate() {
                 let dst = &mut desc.uniform_blocks[idx];
                 dst.size = ub.size as c_size_t;
-                SgShaderDesc::collect_uniforms(dst, &ub.uniforms);
+                dst.layout = ub.layout;
+                SgShaderDesc::collect_uniforms(dst, &ub.uniforms, strings);
             }
         }
 
         fn collect_images(desc: &mut SgShaderStageDesc,
-                          src: &[super::SgShaderImageDesc]) {
+                          src: &[super::SgShaderImageDesc],
+                          strings: &mut SAppStrings) {
             for (idx, img) in src.iter().enumerate() {
                 let dst = &mut desc.images[idx];
 
-                let name = CString::new(img.name).unwrap();
-
-                dst.name = name.into_raw();
+                dst.name = strings.add_or_default(img.name);
                 dst.image_type = img.image_type;
             }
         }
+
+        /// Converts back to a Rust description, e.g. one returned by `sg_query_shader_defaults()`.
+        ///
+        /// Strings are taken from `src` where they are set. All other strings must point
+        /// to static data, like the defaults filled in by sokol_gfx for empty strings.
+        pub fn to_desc<'a>(&self, src: Option<&super::SgShaderDesc<'a>>) -> super::SgShaderDesc<'a> {
+            let attrs = self.attrs.iter()
+                .enumerate()
+                .take_while(|(_, attr)| !attr.name.is_null() || !attr.sem_name.is_null())
+                .map(|(idx, attr)| {
+                    let src = src.and_then(|src| src.attrs.get(idx));
+
+                    super::SgShaderAttrDesc {
+                        name: Self::to_str(attr.name, src.map(|src| src.name)).unwrap_or(""),
+                        sem_name: Self::to_str(attr.sem_name, src.map(|src| src.sem_name)).unwrap_or(""),
+                        sem_index: attr.sem_index,
+                    }
+                })
+                .collect();
+
+            super::SgShaderDesc {
+                attrs,
+                vs: Self::to_stage_desc(&self.vs, src.map(|src| &src.vs)),
+                fs: Self::to_stage_desc(&self.fs, src.map(|src| &src.fs)),
+                label: src.and_then(|src| src.label),
+            }
+        }
+
+        fn to_stage_desc<'a>(stage: &SgShaderStageDesc,
+                             src: Option<&super::SgShaderStageDesc<'a>>) -> super::SgShaderStageDesc<'a> {
+            let uniform_blocks = stage.uniform_blocks.iter()
+                .enumerate()
+                .take_while(|(_, ub)| ub.size > 0)
+                .map(|(idx, ub)| {
+                    let src = src.and_then(|src| src.uniform_blocks.get(idx));
+
+                    let uniforms = ub.uniforms.iter()
+                        .enumerate()
+                        .take_while(|(_, u)| u.uniform_type != super::SgUniformType::_Invalid)
+                        .map(|(idx, u)| {
+                            let src = src.and_then(|src| src.uniforms.get(idx));
+
+                            super::SgShaderUniformDesc {
+                                name: Self::to_str(u.name, src.map(|src| src.name)).unwrap_or(""),
+                                uniform_type: u.uniform_type,
+                                array_count: u.array_count,
+                            }
+                        })
+                        .collect();
+
+                    super::SgShaderUniformBlockDesc {
+                        size: ub.size as i32,
+                        layout: ub.layout,
+                        uniforms,
+                    }
+                })
+                .collect();
+
+            let images = stage.images.iter()
+                .enumerate()
+                .take_while(|(_, img)| img.image_type != super::SgImageType::_Default)
+                .map(|(idx, img)| {
+                    let src = src.and_then(|src| src.images.get(idx));
+
+                    super::SgShaderImageDesc {
+                        name: Self::to_str(img.name, src.map(|src| src.name)).unwrap_or(""),
+                        image_type: img.image_type,
+                    }
+                })
+                .collect();
+
+            super::SgShaderStageDesc {
+                source: Self::to_str(stage.source, src.and_then(|src| src.source)),
+                byte_code: src.and_then(|src| src.byte_code),
+                entry: Self::to_str(stage.entry, src.and_then(|src| src.entry)),
+                uniform_blocks,
+                images,
+            }
+        }
+
+        fn to_str(s: *const c_char, src: Option<&str>) -> Option<&str> {
+            match src {
+                Some(src) if !src.is_empty() => Some(src),
+                _ if !s.is_null() => unsafe {
+                    CStr::from_ptr(s).to_str().ok()
+                },
+                _ => src,
+            }
+        }
     }
 
     #[repr(C)]
@@ -640,7 +817,7 @@ mod ffi {
     }
 
     impl SgPipelineDesc {
-        pub fn make(desc: &super::SgPipelineDesc) -> SgPipelineDesc {
+        pub fn make(desc: &super::SgPipelineDesc, strings: &mut SAppStrings) -> SgPipelineDesc {
             let mut colors = [Default::default(); SG_MAX_COLOR_ATTACHMENTS];
             for (idx, color) in (*desc).colors.iter().enumerate() {
                 colors[idx] = *color;
@@ -660,7 +837,7 @@ mod ffi {
                 sample_count: (*desc).sample_count,
                 blend_color: (*desc).blend_color,
                 alpha_to_coverage_enabled: (*desc).alpha_to_coverage_enabled,
-                label: null(),
+                label: strings.add_opt(desc.label),
                 _end_canary: 0,
             };
 
@@ -691,6 +868,49 @@ mod ffi {
                 };
             }
         }
+
+        pub fn to_desc(&self) -> super::SgPipelineDesc<'static> {
+            let attrs: Vec<super::SgVertexAttrDesc> = self.layout.attrs.iter()
+                .take_while(|attr| attr.format != super::SgVertexFormat::_Invalid)
+                .map(|attr| super::SgVertexAttrDesc {
+                    buffer_index: attr.buffer_index,
+                    offset: attr.offset,
+                    format: attr.format,
+                })
+                .collect();
+
+            let num_buffers = attrs.iter()
+                .map(|attr| attr.buffer_index as usize + 1)
+                .max()
+                .unwrap_or(0);
+
+            let buffers = self.layout.buffers[..num_buffers].iter()
+                .map(|buf| super::SgBufferLayoutDesc {
+                    stride: buf.stride as usize,
+                    step_func: buf.step_func,
+                    step_rate: buf.step_rate,
+                })
+                .collect();
+
+            super::SgPipelineDesc {
+                shader: self.shader,
+                layout: super::SgLayoutDesc {
+                    buffers,
+                    attrs,
+                },
+                depth: self.depth,
+                stencil: self.stencil,
+                colors: self.colors[..self.color_count as usize].to_vec(),
+                primitive_type: self.primitive_type,
+                index_type: self.index_type,
+                cull_mode: self.cull_mode,
+                face_winding: self.face_winding,
+                sample_count: self.sample_count,
+                blend_color: self.blend_color,
+                alpha_to_coverage_enabled: self.alpha_to_coverage_enabled,
+                label: None,
+            }
+        }
     }
 
     #[repr(C)]
@@ -704,12 +924,12 @@ mod ffi {
     }
 
     impl SgPassDesc {
-        pub fn make(desc: &super::SgPassDesc) -> SgPassDesc {
+        pub fn make(desc: &super::SgPassDesc, strings: &mut SAppStrings) -> SgPassDesc {
             let mut pass = SgPassDesc {
                 _start_canary: 0,
                 color_attachments: Default::default(),
                 depth_stencil_attachment: desc.depth_stencil_attachment,
-                label: null(),
+                label: strings.add_opt(desc.label),
                 _end_canary: 0,
             };
 
@@ -719,16 +939,466 @@ mod ffi {
 
             pass
         }
+
+        pub fn to_desc(&self) -> super::SgPassDesc<'static> {
+            super::SgPassDesc {
+                color_attachments: self.color_attachments.iter()
+                    .take_while(|att| att.image.id != 0)
+                    .cloned()
+                    .collect(),
+                depth_stencil_attachment: self.depth_stencil_attachment,
+                label: None,
+            }
+        }
+    }
+
+    mod layout {
+        use sys::raw;
+
+        use super::*;
+        use super::super::{SgBuffer, SgImage, SgShader, SgPipeline, SgPass, SgContext};
+        use super::super::{SgColorAttachmentAction, SgDepthAttachmentAction, SgStencilAttachmentAction};
+        use super::super::{SgBlendState, SgColorState, SgDepthState, SgStencilFaceState, SgStencilState};
+        use super::super::{SgPassAttachmentDesc, SgFeatures};
+        use super::super::{SgContextDesc, SgGLContextDesc, SgMetalContextDesc, SgD3D11ContextDesc, SgWGPUContextDesc};
+
+        assert_layout!(SgBuffer, raw::sg_buffer, { id => id });
+        assert_layout!(SgImage, raw::sg_image, { id => id });
+        assert_layout!(SgShader, raw::sg_shader, { id => id });
+        assert_layout!(SgPipeline, raw::sg_pipeline, { id => id });
+        assert_layout!(SgPass, raw::sg_pass, { id => id });
+        assert_layout!(SgContext, raw::sg_context, { id => id });
+
+        assert_layout!(super::super::SgColor, raw::sg_color);
+
+        assert_layout!(SgColorAttachmentAction, raw::sg_color_attachment_action, {
+            action => action,
+            val => value,
+        });
+
+        assert_layout!(SgDepthAttachmentAction, raw::sg_depth_attachment_action, {
+            action => action,
+            val => value,
+        });
+
+        assert_layout!(SgStencilAttachmentAction, raw::sg_stencil_attachment_action, {
+            action => action,
+            val => value,
+        });
+
+        assert_layout!(SgPassAction, raw::sg_pass_action, {
+            _start_canary => _start_canary,
+            colors => colors,
+            depth => depth,
+            stencil => stencil,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgBindings, raw::sg_bindings, {
+            _start_canary => _start_canary,
+            vertex_buffers => vertex_buffers,
+            vertex_buffer_offsets => vertex_buffer_offsets,
+            index_buffer => index_buffer,
+            index_buffer_offset => index_buffer_offset,
+            vs_images => vs_images,
+            fs_images => fs_images,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgGLContextDesc, raw::sg_gl_context_desc, {
+            force_gles2 => force_gles2,
+        });
+
+        assert_layout!(SgMetalContextDesc, raw::sg_metal_context_desc, {
+            device => device,
+            renderpass_descriptor_cb => renderpass_descriptor_cb,
+            renderpass_descriptor_userdata_cb => renderpass_descriptor_userdata_cb,
+            drawable_cb => drawable_cb,
+            drawable_userdata_cb => drawable_userdata_cb,
+            user_data => user_data,
+        });
+
+        assert_layout!(SgD3D11ContextDesc, raw::sg_d3d11_context_desc, {
+            device => device,
+            device_context => device_context,
+            render_target_view_cb => render_target_view_cb,
+            render_target_view_userdata_cb => render_target_view_userdata_cb,
+            depth_stencil_view_cb => depth_stencil_view_cb,
+            depth_stencil_view_userdata_cb => depth_stencil_view_userdata_cb,
+            user_data => user_data,
+        });
+
+        assert_layout!(SgWGPUContextDesc, raw::sg_wgpu_context_desc, {
+            device => device,
+            render_view_cb => render_view_cb,
+            render_view_userdata_cb => render_view_userdata_cb,
+            resolve_view_cb => resolve_view_cb,
+            resolve_view_userdata_cb => resolve_view_userdata_cb,
+            depth_stencil_view_cb => depth_stencil_view_cb,
+            depth_stencil_view_userdata_cb => depth_stencil_view_userdata_cb,
+            user_data => user_data,
+        });
+
+        assert_layout!(SgContextDesc, raw::sg_context_desc, {
+            color_format => color_format,
+            depth_format => depth_format,
+            sample_count => sample_count,
+            gl => gl,
+            metal => metal,
+            d3d11 => d3d11,
+            wgpu => wgpu,
+        });
+
+        assert_layout!(SgDesc, raw::sg_desc, {
+            _start_canary => _start_canary,
+            buffer_pool_size => buffer_pool_size,
+            image_pool_size => image_pool_size,
+            shader_pool_size => shader_pool_size,
+            pipeline_pool_size => pipeline_pool_size,
+            pass_pool_size => pass_pool_size,
+            context_pool_size => context_pool_size,
+            uniform_buffer_size => uniform_buffer_size,
+            staging_buffer_size => staging_buffer_size,
+            sampler_cache_size => sampler_cache_size,
+            max_commit_listeners => max_commit_listeners,
+            disable_validation => disable_validation,
+            allocator => allocator,
+            logger => logger,
+            context => context,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgRange, raw::sg_range, {
+            ptr => ptr,
+            size => size,
+        });
+
+        assert_layout!(SgBufferDesc, raw::sg_buffer_desc, {
+            _start_canary => _start_canary,
+            size => size,
+            buffer_type => type_,
+            usage => usage,
+            data => data,
+            label => label,
+            gl_buffers => gl_buffers,
+            mtl_buffers => mtl_buffers,
+            d3d11_buffer => d3d11_buffer,
+            wgpu_buffer => wgpu_buffer,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgImageData, raw::sg_image_data, {
+            subimage => subimage,
+        });
+
+        assert_layout!(SgImageDesc, raw::sg_image_desc, {
+            _start_canary => _start_canary,
+            image_type => type_,
+            render_target => render_target,
+            width => width,
+            height => height,
+            num_slices => num_slices,
+            num_mipmaps => num_mipmaps,
+            usage => usage,
+            pixel_format => pixel_format,
+            sample_count => sample_count,
+            min_filter => min_filter,
+            mag_filter => mag_filter,
+            wrap_u => wrap_u,
+            wrap_v => wrap_v,
+            wrap_w => wrap_w,
+            border_color => border_color,
+            max_anisotropy => max_anisotropy,
+            min_lod => min_lod,
+            max_lod => max_lod,
+            data => data,
+            label => label,
+            gl_textures => gl_textures,
+            gl_texture_target => gl_texture_target,
+            mtl_textures => mtl_textures,
+            d3d11_texture => d3d11_texture,
+            d3d11_shader_resource_view => d3d11_shader_resource_view,
+            wgpu_texture => wgpu_texture,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgShaderAttrDesc, raw::sg_shader_attr_desc, {
+            name => name,
+            sem_name => sem_name,
+            sem_index => sem_index,
+        });
+
+        assert_layout!(SgShaderUniformDesc, raw::sg_shader_uniform_desc, {
+            name => name,
+            uniform_type => type_,
+            array_count => array_count,
+        });
+
+        assert_layout!(SgShaderUniformBlockDesc, raw::sg_shader_uniform_block_desc, {
+            size => size,
+            layout => layout,
+            uniforms => uniforms,
+        });
+
+        assert_layout!(SgShaderImageDesc, raw::sg_shader_image_desc, {
+            name => name,
+            image_type => image_type,
+            sampler_type => sampler_type,
+        });
+
+        assert_layout!(SgShaderStageDesc, raw::sg_shader_stage_desc, {
+            source => source,
+            bytecode => bytecode,
+            entry => entry,
+            d3d11_target => d3d11_target,
+            uniform_blocks => uniform_blocks,
+            images => images,
+        });
+
+        assert_layout!(SgShaderDesc, raw::sg_shader_desc, {
+            _start_canary => _start_canary,
+            attrs => attrs,
+            vs => vs,
+            fs => fs,
+            label => label,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgBufferLayoutDesc, raw::sg_buffer_layout_desc, {
+            stride => stride,
+            step_func => step_func,
+            step_rate => step_rate,
+        });
+
+        assert_layout!(SgVertexAttrDesc, raw::sg_vertex_attr_desc, {
+            buffer_index => buffer_index,
+            offset => offset,
+            format => format,
+        });
+
+        assert_layout!(SgLayoutDesc, raw::sg_layout_desc, {
+            buffers => buffers,
+            attrs => attrs,
+        });
+
+        assert_layout!(SgBlendState, raw::sg_blend_state, {
+            enabled => enabled,
+            src_factor_rgb => src_factor_rgb,
+            dst_factor_rgb => dst_factor_rgb,
+            op_rgb => op_rgb,
+            src_factor_alpha => src_factor_alpha,
+            dst_factor_alpha => dst_factor_alpha,
+            op_alpha => op_alpha,
+        });
+
+        assert_layout!(SgColorState, raw::sg_color_state, {
+            pixel_format => pixel_format,
+            write_mask => write_mask,
+            blend => blend,
+        });
+
+        assert_layout!(SgDepthState, raw::sg_depth_state, {
+            pixel_format => pixel_format,
+            compare => compare,
+            write_enabled => write_enabled,
+            bias => bias,
+            bias_slop_scale => bias_slope_scale,
+            bias_clamp => bias_clamp,
+        });
+
+        assert_layout!(SgStencilFaceState, raw::sg_stencil_face_state, {
+            compare => compare,
+            fail_op => fail_op,
+            depth_fail_op => depth_fail_op,
+            pass_op => pass_op,
+        });
+
+        assert_layout!(SgStencilState, raw::sg_stencil_state, {
+            enabled => enabled,
+            front => front,
+            back => back,
+            read_mask => read_mask,
+            write_mask => write_mask,
+            stencil_ref => ref_,
+        });
+
+        assert_layout!(SgPipelineDesc, raw::sg_pipeline_desc, {
+            _start_canary => _start_canary,
+            shader => shader,
+            layout => layout,
+            depth => depth,
+            stencil => stencil,
+            color_count => color_count,
+            colors => colors,
+            primitive_type => primitive_type,
+            index_type => index_type,
+            cull_mode => cull_mode,
+            face_winding => face_winding,
+            sample_count => sample_count,
+            blend_color => blend_color,
+            alpha_to_coverage_enabled => alpha_to_coverage_enabled,
+            label => label,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgPassAttachmentDesc, raw::sg_pass_attachment_desc, {
+            image => image,
+            mip_level => mip_level,
+            slice => slice,
+        });
+
+        assert_layout!(SgPassDesc, raw::sg_pass_desc, {
+            _start_canary => _start_canary,
+            color_attachments => color_attachments,
+            depth_stencil_attachment => depth_stencil_attachment,
+            label => label,
+            _end_canary => _end_canary,
+        });
+
+        assert_layout!(SgFeatures, raw::sg_features, {
+            Instancing => instancing,
+            OriginTopLeft => origin_top_left,
+            MultipleRenderTarget => multiple_render_targets,
+            MSAARenderTargets => msaa_render_targets,
+            ImageType3D => imagetype_3d,
+            ImageTypeArray => imagetype_array,
+            ImageClampToBorder => image_clamp_to_border,
+            MRTIndependentBlendState => mrt_independent_blend_state,
+            MRTIndependentWriteMask => mrt_independent_write_mask,
+        });
+
+        //
+        // enums: size and number of elements
+        //
+        macro_rules! assert_enum {
+            ($($rust:ident :: $rust_num:ident => $c:ident :: $c_num:ident),* $(,)*) => {
+                $(
+                    assert_layout!(super::super::$rust, raw::$c);
+                    const _: () = assert!(super::super::$rust::$rust_num as u32 == raw::$c::$c_num as u32,
+                                          concat!("enum mismatch: ", stringify!($rust), " vs. ", stringify!($c)));
+                )*
+            };
+        }
+
+        assert_enum!(
+            SgResourceState::Invalid => sg_resource_state::SG_RESOURCESTATE_INVALID,
+            SgUsage::Num => sg_usage::_SG_USAGE_NUM,
+            SgBufferType::Num => sg_buffer_type::_SG_BUFFERTYPE_NUM,
+            SgIndexType::Num => sg_index_type::_SG_INDEXTYPE_NUM,
+            SgImageType::Num => sg_image_type::_SG_IMAGETYPE_NUM,
+            SgCubeFace::Num => sg_cube_face::SG_CUBEFACE_NUM,
+            SgPixelFormat::Num => sg_pixel_format::_SG_PIXELFORMAT_NUM,
+            SgPrimitiveType::Num => sg_primitive_type::_SG_PRIMITIVETYPE_NUM,
+            SgFilter::Num => sg_filter::_SG_FILTER_NUM,
+            SgWrap::Num => sg_wrap::_SG_WRAP_NUM,
+            SgBorderColor::_Num => sg_border_color::_SG_BORDERCOLOR_NUM,
+            SgVertexFormat::_Num => sg_vertex_format::_SG_VERTEXFORMAT_NUM,
+            SgVertexStep::_Num => sg_vertex_step::_SG_VERTEXSTEP_NUM,
+            SgUniformType::_Num => sg_uniform_type::_SG_UNIFORMTYPE_NUM,
+            SgUniformLayout::_Num => sg_uniform_layout::_SG_UNIFORMLAYOUT_NUM,
+            SgCullMode::_Num => sg_cull_mode::_SG_CULLMODE_NUM,
+            SgFaceWinding::_Num => sg_face_winding::_SG_FACEWINDING_NUM,
+            SgCompareFunc::_Num => sg_compare_func::_SG_COMPAREFUNC_NUM,
+            SgStencilOp::_Num => sg_stencil_op::_SG_STENCILOP_NUM,
+            SgBlendFactor::_Num => sg_blend_factor::_SG_BLENDFACTOR_NUM,
+            SgBlendOp::_Num => sg_blend_op::_SG_BLENDOP_NUM,
+            SgAction::_Num => sg_action::_SG_ACTION_NUM,
+        );
+
+        assert_layout!(super::super::SgBackend, raw::sg_backend);
+        assert_layout!(super::super::SgSamplerType, raw::sg_sampler_type);
+        assert_layout!(super::super::SgShaderStage, raw::sg_shader_stage);
+
+        const _: () = assert!(SG_NUM_INFLIGHT_FRAMES == raw::SG_NUM_INFLIGHT_FRAMES as usize);
+        const _: () = assert!(SG_MAX_COLOR_ATTACHMENTS == raw::SG_MAX_COLOR_ATTACHMENTS as usize);
+        const _: () = assert!(SG_MAX_SHADERSTAGE_BUFFERS == raw::SG_MAX_SHADERSTAGE_BUFFERS as usize);
+        const _: () = assert!(SG_MAX_SHADERSTAGE_IMAGES == raw::SG_MAX_SHADERSTAGE_IMAGES as usize);
+        const _: () = assert!(SG_MAX_SHADERSTAGE_UBS == raw::SG_MAX_SHADERSTAGE_UBS as usize);
+        const _: () = assert!(SG_MAX_UB_MEMBERS == raw::SG_MAX_UB_MEMBERS as usize);
+        const _: () = assert!(SG_MAX_VERTEX_ATTRIBUTES == raw::SG_MAX_VERTEX_ATTRIBUTES as usize);
+        const _: () = assert!(SG_MAX_MIPMAPS == raw::SG_MAX_MIPMAPS as usize);
+
+        assert_layout!(super::super::SgPixelFormatInfo, raw::sg_pixelformat_info, {
+            sample => sample,
+            filter => filter,
+            render => render,
+            blend => blend,
+            msaa => msaa,
+            depth => depth,
+        });
+
+        assert_layout!(super::super::SgLimits, raw::sg_limits, {
+            max_image_size_2d => max_image_size_2d,
+            max_image_size_cube => max_image_size_cube,
+            max_image_size_3d => max_image_size_3d,
+            max_image_size_array => max_image_size_array,
+            max_image_array_layers => max_image_array_layers,
+            max_vertex_attrs => max_vertex_attrs,
+            gl_max_vertex_uniform_vectors => gl_max_vertex_uniform_vectors,
+        });
+
+        assert_layout!(super::super::SgSlotInfo, raw::sg_slot_info, {
+            state => state,
+            res_id => res_id,
+            ctx_id => ctx_id,
+        });
+
+        assert_layout!(super::super::SgBufferInfo, raw::sg_buffer_info, {
+            slot => slot,
+            update_frame_index => update_frame_index,
+            append_frame_index => append_frame_index,
+            append_pos => append_pos,
+            append_overflow => append_overflow,
+            num_slots => num_slots,
+            active_slot => active_slot,
+        });
+
+        assert_layout!(super::super::SgImageInfo, raw::sg_image_info, {
+            slot => slot,
+            upd_frame_index => upd_frame_index,
+            num_slots => num_slots,
+            active_slot => active_slot,
+            width => width,
+            height => height,
+        });
+
+        assert_layout!(super::super::SgShaderInfo, raw::sg_shader_info, { slot => slot });
+        assert_layout!(super::super::SgPipelineInfo, raw::sg_pipeline_info, { slot => slot });
+        assert_layout!(super::super::SgPassInfo, raw::sg_pass_info, { slot => slot });
+
+        assert_layout!(SgCommitListener, raw::sg_commit_listener, {
+            func => func,
+            user_data => user_data,
+        });
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct SgCommitListener {
+        pub func: Option<extern fn(*mut c_void)>,
+        pub user_data: *mut c_void,
+    }
+
+    impl SgCommitListener {
+        pub fn make(listener: &super::SgCommitListener) -> Self {
+            SgCommitListener {
+                func: Some(super::sg_commit_listener_func),
+                user_data: listener.callback as *mut c_void,
+            }
+        }
     }
 
     extern {
-        fn sapp_sgcontext() -> SgContextDesc;
+        #[cfg(not(feature = "dummy"))]
+        fn sapp_sgcontext() -> super::SgContextDesc;
         pub fn sg_setup(desc: *const SgDesc);
         pub fn sg_shutdown();
         pub fn sg_isvalid() -> bool;
         pub fn sg_query_desc() -> SgDesc;
         pub fn sg_query_backend() -> super::SgBackend;
         pub fn sg_query_features() -> super::SgFeatures;
+        pub fn sg_query_limits() -> super::SgLimits;
+        pub fn sg_query_pixelformat(fmt: super::SgPixelFormat) -> super::SgPixelFormatInfo;
         pub fn sg_reset_state_cache();
 
         pub fn sg_make_buffer(desc: *const SgBufferDesc) -> super::SgBuffer;
@@ -743,10 +1413,41 @@ mod ffi {
         pub fn sg_destroy_pipeline(pip: super::SgPipeline);
         pub fn sg_destroy_pass(pass: super::SgPass);
 
+        pub fn sg_alloc_buffer() -> super::SgBuffer;
+        pub fn sg_alloc_image() -> super::SgImage;
+        pub fn sg_alloc_shader() -> super::SgShader;
+        pub fn sg_alloc_pipeline() -> super::SgPipeline;
+        pub fn sg_alloc_pass() -> super::SgPass;
+
+        pub fn sg_dealloc_buffer(buf: super::SgBuffer);
+        pub fn sg_dealloc_image(img: super::SgImage);
+        pub fn sg_dealloc_shader(shd: super::SgShader);
+        pub fn sg_dealloc_pipeline(pip: super::SgPipeline);
+        pub fn sg_dealloc_pass(pass: super::SgPass);
+
+        pub fn sg_init_buffer(buf: super::SgBuffer, desc: *const SgBufferDesc);
+        pub fn sg_init_image(img: super::SgImage, desc: *const SgImageDesc);
+        pub fn sg_init_shader(shd: super::SgShader, desc: *const SgShaderDesc);
+        pub fn sg_init_pipeline(pip: super::SgPipeline, desc: *const SgPipelineDesc);
+        pub fn sg_init_pass(pass: super::SgPass, desc: *const SgPassDesc);
+
+        pub fn sg_uninit_buffer(buf: super::SgBuffer) -> bool;
+        pub fn sg_uninit_image(img: super::SgImage) -> bool;
+        pub fn sg_uninit_shader(shd: super::SgShader) -> bool;
+        pub fn sg_uninit_pipeline(pip: super::SgPipeline) -> bool;
+        pub fn sg_uninit_pass(pass: super::SgPass) -> bool;
+
+        pub fn sg_fail_buffer(buf: super::SgBuffer);
+        pub fn sg_fail_image(img: super::SgImage);
+        pub fn sg_fail_shader(shd: super::SgShader);
+        pub fn sg_fail_pipeline(pip: super::SgPipeline);
+        pub fn sg_fail_pass(pass: super::SgPass);
+
         pub fn sg_update_buffer(buf: super::SgBuffer, data: *const SgRange);
         pub fn sg_update_image(img: super::SgImage, data: *const SgImageData);
-        pub fn sg_append_buffer(buf: super::SgBuffer, data_ptr: *const c_void, data_size: c_int) -> c_int;
+        pub fn sg_append_buffer(buf: super::SgBuffer, data: *const SgRange) -> c_int;
         pub fn sg_query_buffer_overflow(buf: super::SgBuffer) -> bool;
+        pub fn sg_query_buffer_will_overflow(buf: super::SgBuffer, size: c_size_t) -> bool;
 
         pub fn sg_query_buffer_state(buf: super::SgBuffer) -> super::SgResourceState;
         pub fn sg_query_image_state(img: super::SgImage) -> super::SgResourceState;
@@ -754,6 +1455,24 @@ mod ffi {
         pub fn sg_query_pipeline_state(pip: super::SgPipeline) -> super::SgResourceState;
         pub fn sg_query_pass_state(pass: super::SgPass) -> super::SgResourceState;
 
+        pub fn sg_query_buffer_info(buf: super::SgBuffer) -> super::SgBufferInfo;
+        pub fn sg_query_image_info(img: super::SgImage) -> super::SgImageInfo;
+        pub fn sg_query_shader_info(shd: super::SgShader) -> super::SgShaderInfo;
+        pub fn sg_query_pipeline_info(pip: super::SgPipeline) -> super::SgPipelineInfo;
+        pub fn sg_query_pass_info(pass: super::SgPass) -> super::SgPassInfo;
+
+        pub fn sg_query_buffer_desc(buf: super::SgBuffer) -> SgBufferDesc;
+        pub fn sg_query_image_desc(img: super::SgImage) -> SgImageDesc;
+        pub fn sg_query_shader_desc(shd: super::SgShader) -> SgShaderDesc;
+        pub fn sg_query_pipeline_desc(pip: super::SgPipeline) -> SgPipelineDesc;
+        pub fn sg_query_pass_desc(pass: super::SgPass) -> SgPassDesc;
+
+        pub fn sg_query_buffer_defaults(desc: *const SgBufferDesc) -> SgBufferDesc;
+        pub fn sg_query_image_defaults(desc: *const SgImageDesc) -> SgImageDesc;
+        pub fn sg_query_shader_defaults(desc: *const SgShaderDesc) -> SgShaderDesc;
+        pub fn sg_query_pipeline_defaults(desc: *const SgPipelineDesc) -> SgPipelineDesc;
+        pub fn sg_query_pass_defaults(desc: *const SgPassDesc) -> SgPassDesc;
+
         pub fn sg_begin_default_pass(pass_action: *const SgPassAction,
                                      width: c_int,
                                      height: c_int);
@@ -776,6 +1495,16 @@ mod ffi {
         pub fn sg_end_pass();
 
         pub fn sg_commit();
+
+        pub fn sg_setup_context() -> super::SgContext;
+        pub fn sg_activate_context(ctx_id: super::SgContext);
+        pub fn sg_discard_context(ctx_id: super::SgContext);
+
+        pub fn sg_push_debug_group(name: *const c_char);
+        pub fn sg_pop_debug_group();
+
+        pub fn sg_add_commit_listener(listener: SgCommitListener) -> bool;
+        pub fn sg_remove_commit_listener(listener: SgCommitListener) -> bool;
     }
 }
 
@@ -784,37 +1513,37 @@ mod ffi {
 */
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgBuffer {
     id: i32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgImage {
     id: i32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgShader {
     id: i32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgPipeline {
     id: i32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgPass {
     id: i32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct SgContext {
     id: i32,
 }
@@ -824,7 +1553,7 @@ pub struct SgContext {
 */
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgBackend {
     GLCORE33,
     GLES2,
@@ -837,6 +1566,16 @@ pub enum SgBackend {
     Dummy,
 }
 
+impl SgBackend {
+    pub fn is_gl(self) -> bool {
+        matches!(self, SgBackend::GLCORE33 | SgBackend::GLES2 | SgBackend::GLES3)
+    }
+
+    pub fn is_metal(self) -> bool {
+        matches!(self, SgBackend::MetalIOS | SgBackend::MetalMacOS | SgBackend::MetalSimulator)
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct SgFeatures {
@@ -852,7 +1591,37 @@ pub struct SgFeatures {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgPixelFormatInfo {
+    /// The pixel format can be sampled in shaders.
+    pub sample: bool,
+    /// The pixel format can be sampled with filtering.
+    pub filter: bool,
+    /// The pixel format can be used as render target.
+    pub render: bool,
+    /// Alpha-blending is supported.
+    pub blend: bool,
+    /// The pixel format can be used as MSAA render target.
+    pub msaa: bool,
+    /// The pixel format is a depth format.
+    pub depth: bool,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgLimits {
+    pub max_image_size_2d: i32,
+    pub max_image_size_cube: i32,
+    pub max_image_size_3d: i32,
+    pub max_image_size_array: i32,
+    pub max_image_array_layers: i32,
+    pub max_vertex_attrs: i32,
+    /// Only set on GL backends, `0` otherwise.
+    pub gl_max_vertex_uniform_vectors: i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgResourceState {
     Initial,
     Alloc,
@@ -862,8 +1631,73 @@ pub enum SgResourceState {
     ForceU32 = 0x7FFFFFFF,
 }
 
+impl Default for SgResourceState {
+    fn default() -> Self {
+        SgResourceState::Initial
+    }
+}
+
+/*
+    resource info
+*/
+
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgSlotInfo {
+    pub state: SgResourceState,
+    pub res_id: u32,
+    pub ctx_id: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgBufferInfo {
+    pub slot: SgSlotInfo,
+    /// Frame index of the last `sg_update_buffer()`.
+    pub update_frame_index: u32,
+    /// Frame index of the last `sg_append_buffer()`.
+    pub append_frame_index: u32,
+    /// Current position for the next `sg_append_buffer()`.
+    pub append_pos: i32,
+    pub append_overflow: bool,
+    /// Number of renaming slots for dynamically updated buffers.
+    pub num_slots: i32,
+    pub active_slot: i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgImageInfo {
+    pub slot: SgSlotInfo,
+    /// Frame index of the last `sg_update_image()`.
+    pub upd_frame_index: u32,
+    /// Number of renaming slots for dynamically updated images.
+    pub num_slots: i32,
+    pub active_slot: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgShaderInfo {
+    pub slot: SgSlotInfo,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgPipelineInfo {
+    pub slot: SgSlotInfo,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgPassInfo {
+    pub slot: SgSlotInfo,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgUsage {
     _Default,
     Immutable,
@@ -880,7 +1714,7 @@ impl Default for SgUsage {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgBufferType {
     _Default,
     VertexBuffer,
@@ -896,7 +1730,7 @@ impl Default for SgBufferType {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgIndexType {
     _Default,
     None,
@@ -913,7 +1747,7 @@ impl Default for SgIndexType {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgImageType {
     _Default,
     Texture2D,
@@ -931,7 +1765,7 @@ impl Default for SgImageType {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgSamplerType {
     _Default,
     Float,
@@ -946,7 +1780,7 @@ impl Default for SgSamplerType {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgCubeFace {
     PosX,
     NegX,
@@ -959,8 +1793,13 @@ pub enum SgCubeFace {
 }
 const SG_CUBEFACE_NUM: usize = SgCubeFace::Num as usize;
 
+pub const SG_MAX_MIPMAPS: usize = 16;
+
+/// Number of resource slots of `SgUsage::Dynamic` and `SgUsage::Stream` buffers and images.
+pub const SG_NUM_INFLIGHT_FRAMES: usize = 2;
+
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgShaderStage {
     Vertex,
     Fragment,
@@ -968,7 +1807,7 @@ pub enum SgShaderStage {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgPixelFormat {
     _Default,    /* value 0 reserved for default-init */
     None,
@@ -1054,7 +1893,7 @@ impl Default for SgPixelFormat {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgPrimitiveType {
     _Default,
     Points,
@@ -1073,7 +1912,7 @@ impl Default for SgPrimitiveType {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgFilter {
     _Default,
     Nearest,
@@ -1093,7 +1932,7 @@ impl Default for SgFilter {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgWrap {
     _Default,
     Repeat,
@@ -1111,7 +1950,7 @@ impl Default for SgWrap {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgBorderColor {
     _Default,
     TransparentBlack,
@@ -1128,7 +1967,7 @@ impl Default for SgBorderColor {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgVertexFormat {
     _Invalid,
     Float,
@@ -1159,7 +1998,7 @@ impl Default for SgVertexFormat {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgVertexStep {
     _Default,
     PerVertex,
@@ -1175,7 +2014,7 @@ impl Default for SgVertexStep {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgUniformType {
     _Invalid,
     Float,
@@ -1198,7 +2037,7 @@ impl Default for SgUniformType {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgUniformLayout {
     _Default,
     Native,
@@ -1215,7 +2054,7 @@ impl Default for SgUniformLayout {
 
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgCullMode {
     _Default,
     None,
@@ -1232,7 +2071,7 @@ impl Default for SgCullMode {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgFaceWinding {
     _Default,
     CCW,
@@ -1248,7 +2087,7 @@ impl Default for SgFaceWinding {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgCompareFunc {
     _Default,
     Never,
@@ -1270,7 +2109,7 @@ impl Default for SgCompareFunc {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgStencilOp {
     _Default,
     Keep,
@@ -1292,7 +2131,7 @@ impl Default for SgStencilOp {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgBlendFactor {
     _Default,
     Zero,
@@ -1321,7 +2160,7 @@ impl Default for SgBlendFactor {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgBlendOp {
     _Default,
     Add,
@@ -1339,6 +2178,7 @@ impl Default for SgBlendOp {
 
 bitflags! {
     #[derive(Default)]
+    #[repr(transparent)]
     pub struct SgColorMask: u32 {
         const _Default = 0x0;
         const NONE = 0x10;
@@ -1362,7 +2202,7 @@ bitflags! {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum SgAction {
     _Default,
     Clear,
@@ -1422,7 +2262,6 @@ pub struct SgBindings {
     pub fs_images: Vec<SgImage>,
 }
 
-#[repr(C)]
 #[derive(Copy, Clone, Default, Debug)]
 pub struct SgDesc {
     pub buffer_pool_size: i32,
@@ -1436,19 +2275,73 @@ pub struct SgDesc {
     pub sampler_cache_size: i32,
     pub max_commit_listeners: i32,
     pub disable_validation: bool,
+    /// The render target and backend objects to render with. If `None`, `sg_setup()`
+    /// uses the window of sokol_app, so it must be called from `sapp_run()`.
+    ///
+    /// Set this to render to a window or GL context created by another library.
+    pub context: Option<SgContextDesc>,
 }
 
 #[derive(Default, Debug)]
-pub struct SgBufferDesc {
+pub struct SgBufferDesc<'a> {
     pub size: usize,
     pub buffer_type: SgBufferType,
     pub usage: SgUsage,
+    pub label: Option<&'a str>,
+    /// Wraps existing native buffers instead of creating new ones.
+    pub native: Option<SgNativeBuffer>,
 }
 
 pub const SG_BUFFER_CONTENT_NONE: Option<&u8> = None;
 
+/// Native buffers created outside of sokol_gfx, by the render API of the active backend.
+///
+/// sokol_gfx doesn't take ownership: the native buffers must outlive the `SgBuffer`, and
+/// are not released by `sg_destroy_buffer()`. `SgUsage::Immutable` buffers only use the
+/// first slot, dynamic and stream buffers need `SG_NUM_INFLIGHT_FRAMES` native buffers.
+#[derive(Copy, Clone, Debug)]
+pub enum SgNativeBuffer {
+    /// GL buffer names.
+    GL([u32; SG_NUM_INFLIGHT_FRAMES]),
+    /// `id<MTLBuffer>` objects.
+    Metal([*const c_void; SG_NUM_INFLIGHT_FRAMES]),
+    /// An `ID3D11Buffer` object.
+    D3D11(*const c_void),
+    /// A `WGPUBuffer` object.
+    WGPU(*const c_void),
+}
+
+impl SgNativeBuffer {
+    /// Wraps a single GL buffer, for `SgUsage::Immutable` buffers.
+    pub fn gl(buffer: u32) -> Self {
+        let mut buffers = [0; SG_NUM_INFLIGHT_FRAMES];
+        buffers[0] = buffer;
+        SgNativeBuffer::GL(buffers)
+    }
+
+    pub fn is_compatible(&self, backend: SgBackend) -> bool {
+        match self {
+            SgNativeBuffer::GL(_) => backend.is_gl(),
+            SgNativeBuffer::Metal(_) => backend.is_metal(),
+            SgNativeBuffer::D3D11(_) => backend == SgBackend::D3D11,
+            SgNativeBuffer::WGPU(_) => backend == SgBackend::WGPU,
+        }
+    }
+
+    /// Checks that the native buffers belong to the render API of the active backend.
+    pub fn check_backend(&self) -> Result<(), SgError> {
+        let backend = sg_query_backend();
+
+        if !self.is_compatible(backend) {
+            return Err(SgError::NativeBackend { resource: "buffers", backend });
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Default, Debug)]
-pub struct SgImageDesc {
+pub struct SgImageDesc<'a> {
     pub image_type: SgImageType,
     pub render_target: bool,
     pub width: i32,
@@ -1467,9 +2360,72 @@ pub struct SgImageDesc {
     pub max_anisotropy: u32,
     pub min_lod: f32,
     pub max_lod: f32,
+    pub label: Option<&'a str>,
+    /// Wraps existing native textures instead of creating new ones.
+    pub native: Option<SgNativeImage>,
 }
 
-pub const SG_IMAGE_CONTENT_NONE: Option<Vec<Vec<(*const u8, i32)>>> = None;
+pub const SG_IMAGE_CONTENT_NONE: Option<&SgImageContent<'static>> = None;
+
+/// Native textures created outside of sokol_gfx, by the render API of the active backend.
+///
+/// sokol_gfx doesn't take ownership: the native textures must outlive the `SgImage`, and
+/// are not released by `sg_destroy_image()`. The `SgImageDesc` must still describe the
+/// texture, i.e. its type, size, pixel format and number of mipmaps. `SgUsage::Immutable`
+/// images only use the first slot, dynamic and stream images need `SG_NUM_INFLIGHT_FRAMES`
+/// native textures.
+#[derive(Copy, Clone, Debug)]
+pub enum SgNativeImage {
+    GL {
+        /// GL texture names.
+        textures: [u32; SG_NUM_INFLIGHT_FRAMES],
+        /// The texture target, e.g. `GL_TEXTURE_EXTERNAL_OES`. If `0`, the target is
+        /// derived from the image type.
+        target: u32,
+    },
+    /// `id<MTLTexture>` objects.
+    Metal([*const c_void; SG_NUM_INFLIGHT_FRAMES]),
+    D3D11 {
+        /// An `ID3D11Texture2D` or `ID3D11Texture3D` object.
+        texture: *const c_void,
+        /// An optional `ID3D11ShaderResourceView`, created by sokol_gfx if null.
+        shader_resource_view: *const c_void,
+    },
+    /// A `WGPUTexture` object.
+    WGPU(*const c_void),
+}
+
+impl SgNativeImage {
+    /// Wraps a single GL texture, for `SgUsage::Immutable` images.
+    pub fn gl(texture: u32) -> Self {
+        let mut textures = [0; SG_NUM_INFLIGHT_FRAMES];
+        textures[0] = texture;
+        SgNativeImage::GL {
+            textures,
+            target: 0,
+        }
+    }
+
+    pub fn is_compatible(&self, backend: SgBackend) -> bool {
+        match self {
+            SgNativeImage::GL { .. } => backend.is_gl(),
+            SgNativeImage::Metal(_) => backend.is_metal(),
+            SgNativeImage::D3D11 { .. } => backend == SgBackend::D3D11,
+            SgNativeImage::WGPU(_) => backend == SgBackend::WGPU,
+        }
+    }
+
+    /// Checks that the native textures belong to the render API of the active backend.
+    pub fn check_backend(&self) -> Result<(), SgError> {
+        let backend = sg_query_backend();
+
+        if !self.is_compatible(backend) {
+            return Err(SgError::NativeBackend { resource: "textures", backend });
+        }
+
+        Ok(())
+    }
+}
 
 #[derive(Default, Debug)]
 pub struct SgShaderAttrDesc<'a> {
@@ -1488,6 +2444,7 @@ pub struct SgShaderUniformDesc<'a> {
 #[derive(Default, Debug)]
 pub struct SgShaderUniformBlockDesc<'a> {
     pub size: i32,
+    pub layout: SgUniformLayout,
     pub uniforms: Vec<SgShaderUniformDesc<'a>>,
 }
 
@@ -1511,6 +2468,7 @@ pub struct SgShaderDesc<'a> {
     pub attrs: Vec<SgShaderAttrDesc<'a>>,
     pub vs: SgShaderStageDesc<'a>,
     pub fs: SgShaderStageDesc<'a>,
+    pub label: Option<&'a str>,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -1585,7 +2543,7 @@ pub struct SgLayoutDesc {
 }
 
 #[derive(Default, Debug)]
-pub struct SgPipelineDesc {
+pub struct SgPipelineDesc<'a> {
     pub shader: SgShader,
     pub layout: SgLayoutDesc,
     pub depth: SgDepthState,
@@ -1598,6 +2556,7 @@ pub struct SgPipelineDesc {
     pub sample_count: i32,
     pub blend_color: SgColor,
     pub alpha_to_coverage_enabled: bool,
+    pub label: Option<&'a str>,
 }
 
 #[repr(C)]
@@ -1645,25 +2604,80 @@ pub struct SgPassAttachmentDesc {
 }
 
 #[derive(Default, Debug)]
-pub struct SgPassDesc {
+pub struct SgPassDesc<'a> {
     pub color_attachments: Vec<SgPassAttachmentDesc>,
     pub depth_stencil_attachment: SgPassAttachmentDesc,
+    pub label: Option<&'a str>,
 }
 
 /*
     functions
 */
 
+/// Sets up sokol_gfx, and creates the first render context.
+///
+/// Without `SgDesc::context`, the render context is queried from sokol_app, so this must
+/// be called from `SApp::sapp_init()`. The `dummy` renderer doesn't need a render context,
+/// so it can be set up without `sapp_run()`, e.g. in tests on machines without GPU or
+/// display.
 pub fn sg_setup(desc: &SgDesc) {
+    owned::sg_next_setup_generation();
+
     unsafe {
         ffi::sg_setup(&ffi::SgDesc::make(desc));
     }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Runs `f` between `sg_setup()` and `sg_shutdown()`.
+///
+/// sokol_gfx keeps its state in globals, so calls are serialized across threads. This
+/// makes it safe to use from unit tests, which run in parallel by default.
+///
+/// ```no_run
+/// use sokol::gfx::*;
+///
+/// sg_run_headless(&SgDesc::default(), || {
+///     let buf = sg_make_buffer(Some(&[0.0f32; 8]), &SgBufferDesc {
+///         size: 32,
+///         ..Default::default()
+///     });
+///     assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+/// });
+/// ```
+#[cfg(feature = "dummy")]
+pub fn sg_run_headless<F, R>(desc: &SgDesc, f: F) -> R
+    where F: FnOnce() -> R {
+    use std::sync::Mutex;
+
+    static HEADLESS_LOCK: Mutex<()> = Mutex::new(());
+
+    struct Shutdown;
+
+    impl Drop for Shutdown {
+        fn drop(&mut self) {
+            sg_shutdown();
+        }
+    }
+
+    // a panicking test poisons the lock, but sokol_gfx has been shut down regardless
+    let _lock = HEADLESS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    sg_setup(desc);
+    let _shutdown = Shutdown;
+
+    f()
 }
 
 pub fn sg_shutdown() {
     unsafe {
         ffi::sg_shutdown();
     }
+
+    trace::sg_trace_shutdown();
+    encoder::sg_encoder_reset();
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_isvalid() -> bool {
@@ -1675,7 +2689,7 @@ pub fn sg_isvalid() -> bool {
 pub fn sg_query_desc() -> SgDesc {
     unsafe {
         let desc = ffi::sg_query_desc();
-        desc.desc
+        desc.desc()
     }
 }
 
@@ -1691,70 +2705,423 @@ pub fn sg_query_features() -> SgFeatures {
     }
 }
 
-pub fn sg_reset_state_cache() {
+pub fn sg_query_limits() -> SgLimits {
     unsafe {
-        ffi::sg_reset_state_cache();
+        ffi::sg_query_limits()
     }
 }
 
-pub fn sg_make_buffer<T>(content: Option<&T>, desc: &SgBufferDesc) -> SgBuffer {
+pub fn sg_query_pixelformat(fmt: SgPixelFormat) -> SgPixelFormatInfo {
     unsafe {
-        ffi::sg_make_buffer(&ffi::SgBufferDesc::make(content, desc))
+        ffi::sg_query_pixelformat(fmt)
     }
 }
 
-pub fn sg_make_image<T>(content: Option<Vec<Vec<(*const T, i32)>>>, desc: &SgImageDesc) -> SgImage {
+pub fn sg_reset_state_cache() {
     unsafe {
-        ffi::sg_make_image(&ffi::SgImageDesc::make(content, desc))
+        ffi::sg_reset_state_cache();
     }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates a buffer, with optional initial content.
+///
+/// Panics if `desc.native` doesn't belong to the active backend, `sg_try_make_buffer()`
+/// returns an error instead.
+pub fn sg_make_buffer<T>(content: Option<&T>, desc: &SgBufferDesc) -> SgBuffer {
+    expect_valid("sg_make_buffer", check_native_buffer(desc), "sg_try_make_buffer()");
+
+    let mut strings = SAppStrings::default();
+
+    let id = unsafe {
+        ffi::sg_make_buffer(&ffi::SgBufferDesc::make(content, desc, &mut strings))
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Creates an image, with optional initial content.
+///
+/// Panics if the content doesn't match the image, see `SgImageContent::validate()`, or if
+/// `desc.native` doesn't belong to the active backend. `sg_try_make_image()` returns an
+/// error instead.
+pub fn sg_make_image(content: Option<&SgImageContent>, desc: &SgImageDesc) -> SgImage {
+    expect_valid("sg_make_image", check_image(content, desc), "sg_try_make_image()");
+
+    let mut strings = SAppStrings::default();
+
+    let id = unsafe {
+        ffi::sg_make_image(&ffi::SgImageDesc::make(content, desc, &mut strings))
+    };
+
+    crate::log::slog_resume_panic();
+    id
 }
 
+/// Creates a shader.
+///
+/// Panics if `desc` is invalid, see `SgShaderDesc::validate()`. `sg_try_make_shader()`
+/// returns an error instead.
 pub fn sg_make_shader(desc: &SgShaderDesc) -> SgShader {
-    unsafe {
-        ffi::sg_make_shader(&ffi::SgShaderDesc::make(desc))
-    }
+    expect_valid("sg_make_shader", desc.validate(), "sg_try_make_shader()");
+
+    let mut strings = SAppStrings::default();
+
+    let id = unsafe {
+        ffi::sg_make_shader(&ffi::SgShaderDesc::make(desc, &mut strings))
+    };
+
+    crate::log::slog_resume_panic();
+    id
 }
 
+/// Creates a pipeline.
+///
+/// Panics if `desc` is invalid, see `SgPipelineDesc::validate()`. `sg_try_make_pipeline()`
+/// returns an error instead.
 pub fn sg_make_pipeline(desc: &SgPipelineDesc) -> SgPipeline {
-    unsafe {
-        ffi::sg_make_pipeline(&ffi::SgPipelineDesc::make(desc))
-    }
+    expect_valid("sg_make_pipeline", desc.validate(), "sg_try_make_pipeline()");
+
+    let mut strings = SAppStrings::default();
+
+    let id = unsafe {
+        ffi::sg_make_pipeline(&ffi::SgPipelineDesc::make(desc, &mut strings))
+    };
+
+    crate::log::slog_resume_panic();
+    id
 }
 
+/// Creates a pass.
+///
+/// Panics if `desc` is invalid, see `SgPassDesc::validate()`. `sg_try_make_pass()`
+/// returns an error instead.
 pub fn sg_make_pass(desc: &SgPassDesc) -> SgPass {
-    unsafe {
-        ffi::sg_make_pass(&ffi::SgPassDesc::make(desc))
-    }
-}
+    expect_valid("sg_make_pass", desc.validate(), "sg_try_make_pass()");
+
+    let mut strings = SAppStrings::default();
+
+    let id = unsafe {
+        ffi::sg_make_pass(&ffi::SgPassDesc::make(desc, &mut strings))
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
 
 pub fn sg_destroy_buffer(buf: SgBuffer) {
     unsafe {
         ffi::sg_destroy_buffer(buf);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_destroy_image(img: SgImage) {
     unsafe {
         ffi::sg_destroy_image(img);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_destroy_shader(shd: SgShader) {
     unsafe {
         ffi::sg_destroy_shader(shd);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_destroy_pipeline(pip: SgPipeline) {
     unsafe {
         ffi::sg_destroy_pipeline(pip);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_destroy_pass(pass: SgPass) {
     unsafe {
         ffi::sg_destroy_pass(pass);
     }
+
+    crate::log::slog_resume_panic();
+}
+
+/*
+    separate resource allocation and initialization
+
+    A handle returned by `sg_alloc_*()` is in `SgResourceState::Alloc` state until it
+    is initialized with `sg_init_*()`, or marked as failed with `sg_fail_*()`. This
+    allows to hand out handles before the resource data is available, e.g. while it
+    is loaded on a worker thread. Rendering code can check `sg_query_*_state()` and
+    use a placeholder meanwhile.
+
+    All of these functions must be called on the thread which called `sg_setup()`.
+*/
+
+pub fn sg_alloc_buffer() -> SgBuffer {
+    let id = unsafe {
+        ffi::sg_alloc_buffer()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+pub fn sg_alloc_image() -> SgImage {
+    let id = unsafe {
+        ffi::sg_alloc_image()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+pub fn sg_alloc_shader() -> SgShader {
+    let id = unsafe {
+        ffi::sg_alloc_shader()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+pub fn sg_alloc_pipeline() -> SgPipeline {
+    let id = unsafe {
+        ffi::sg_alloc_pipeline()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+pub fn sg_alloc_pass() -> SgPass {
+    let id = unsafe {
+        ffi::sg_alloc_pass()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Releases a handle in `SgResourceState::Alloc` state.
+pub fn sg_dealloc_buffer(buf: SgBuffer) {
+    unsafe {
+        ffi::sg_dealloc_buffer(buf);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Releases a handle in `SgResourceState::Alloc` state.
+pub fn sg_dealloc_image(img: SgImage) {
+    unsafe {
+        ffi::sg_dealloc_image(img);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Releases a handle in `SgResourceState::Alloc` state.
+pub fn sg_dealloc_shader(shd: SgShader) {
+    unsafe {
+        ffi::sg_dealloc_shader(shd);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Releases a handle in `SgResourceState::Alloc` state.
+pub fn sg_dealloc_pipeline(pip: SgPipeline) {
+    unsafe {
+        ffi::sg_dealloc_pipeline(pip);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Releases a handle in `SgResourceState::Alloc` state.
+pub fn sg_dealloc_pass(pass: SgPass) {
+    unsafe {
+        ffi::sg_dealloc_pass(pass);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates the buffer for a handle in `SgResourceState::Alloc` state.
+///
+/// Panics if `desc.native` doesn't belong to the active backend, which
+/// `SgBufferDesc::validate()` checks.
+pub fn sg_init_buffer<T>(buf: SgBuffer, content: Option<&T>, desc: &SgBufferDesc) {
+    expect_valid("sg_init_buffer", check_native_buffer(desc), "SgBufferDesc::validate()");
+
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_init_buffer(buf, &ffi::SgBufferDesc::make(content, desc, &mut strings));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates the image for a handle in `SgResourceState::Alloc` state.
+///
+/// Panics if the content doesn't match the image, see `SgImageContent::validate()`, or if
+/// `desc.native` doesn't belong to the active backend, which `SgImageDesc::validate()` checks.
+pub fn sg_init_image(img: SgImage, content: Option<&SgImageContent>, desc: &SgImageDesc) {
+    expect_valid("sg_init_image", check_image(content, desc),
+                 "SgImageDesc::validate() and SgImageContent::validate()");
+
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_init_image(img, &ffi::SgImageDesc::make(content, desc, &mut strings));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates the shader for a handle in `SgResourceState::Alloc` state.
+pub fn sg_init_shader(shd: SgShader, desc: &SgShaderDesc) {
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_init_shader(shd, &ffi::SgShaderDesc::make(desc, &mut strings));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates the pipeline for a handle in `SgResourceState::Alloc` state.
+pub fn sg_init_pipeline(pip: SgPipeline, desc: &SgPipelineDesc) {
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_init_pipeline(pip, &ffi::SgPipelineDesc::make(desc, &mut strings));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Creates the pass for a handle in `SgResourceState::Alloc` state.
+pub fn sg_init_pass(pass: SgPass, desc: &SgPassDesc) {
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_init_pass(pass, &ffi::SgPassDesc::make(desc, &mut strings));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Destroys the buffer of a handle, moving it back to `SgResourceState::Alloc` state.
+///
+/// Returns `false` if the handle was not in `Valid` or `Failed` state.
+pub fn sg_uninit_buffer(buf: SgBuffer) -> bool {
+    let id = unsafe {
+        ffi::sg_uninit_buffer(buf)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Destroys the image of a handle, moving it back to `SgResourceState::Alloc` state.
+///
+/// Returns `false` if the handle was not in `Valid` or `Failed` state.
+pub fn sg_uninit_image(img: SgImage) -> bool {
+    let id = unsafe {
+        ffi::sg_uninit_image(img)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Destroys the shader of a handle, moving it back to `SgResourceState::Alloc` state.
+///
+/// Returns `false` if the handle was not in `Valid` or `Failed` state.
+pub fn sg_uninit_shader(shd: SgShader) -> bool {
+    let id = unsafe {
+        ffi::sg_uninit_shader(shd)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Destroys the pipeline of a handle, moving it back to `SgResourceState::Alloc` state.
+///
+/// Returns `false` if the handle was not in `Valid` or `Failed` state.
+pub fn sg_uninit_pipeline(pip: SgPipeline) -> bool {
+    let id = unsafe {
+        ffi::sg_uninit_pipeline(pip)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Destroys the pass of a handle, moving it back to `SgResourceState::Alloc` state.
+///
+/// Returns `false` if the handle was not in `Valid` or `Failed` state.
+pub fn sg_uninit_pass(pass: SgPass) -> bool {
+    let id = unsafe {
+        ffi::sg_uninit_pass(pass)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Moves a handle from `SgResourceState::Alloc` to `Failed` state, e.g. if loading its data failed.
+pub fn sg_fail_buffer(buf: SgBuffer) {
+    unsafe {
+        ffi::sg_fail_buffer(buf);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Moves a handle from `SgResourceState::Alloc` to `Failed` state, e.g. if loading its data failed.
+pub fn sg_fail_image(img: SgImage) {
+    unsafe {
+        ffi::sg_fail_image(img);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Moves a handle from `SgResourceState::Alloc` to `Failed` state, e.g. if loading its data failed.
+pub fn sg_fail_shader(shd: SgShader) {
+    unsafe {
+        ffi::sg_fail_shader(shd);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Moves a handle from `SgResourceState::Alloc` to `Failed` state, e.g. if loading its data failed.
+pub fn sg_fail_pipeline(pip: SgPipeline) {
+    unsafe {
+        ffi::sg_fail_pipeline(pip);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Moves a handle from `SgResourceState::Alloc` to `Failed` state, e.g. if loading its data failed.
+pub fn sg_fail_pass(pass: SgPass) {
+    unsafe {
+        ffi::sg_fail_pass(pass);
+    }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_update_buffer<T>(buf: SgBuffer, content: &T, size: i32) {
@@ -1762,19 +3129,52 @@ pub fn sg_update_buffer<T>(buf: SgBuffer, content: &T, size: i32) {
         let ptr = content as *const T;
         ffi::sg_update_buffer(buf, &ffi::SgRange { ptr: ptr as *const c_void, size: size as usize });
     }
+
+    crate::log::slog_resume_panic();
 }
 
-pub fn sg_update_image<T>(img: SgImage, content: Vec<Vec<(*const T, i32)>>) {
+/// Replaces the content of a dynamic or stream image.
+///
+/// Panics if the content doesn't match the image, see `SgImageContent::validate()`.
+/// `sg_try_update_image()` returns an error instead.
+pub fn sg_update_image(img: SgImage, content: &SgImageContent) {
+    let desc = sg_query_image_desc(img);
+    expect_valid("sg_update_image", content.validate(&desc).map_err(SgError::from), "sg_try_update_image()");
+
     unsafe {
         ffi::sg_update_image(img, &ffi::SgImageData::make(Some(content)));
     }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Panics if `result` is an error, naming the function which returns it instead.
+fn expect_valid(func: &str, result: Result<(), SgError>, instead: &str) {
+    if let Err(err) = result {
+        panic!("{}: {}, use {} to handle this error", func, err, instead);
+    }
+}
+
+fn check_native_buffer(desc: &SgBufferDesc) -> Result<(), SgError> {
+    desc.native.map_or(Ok(()), |native| native.check_backend())
+}
+
+fn check_image(content: Option<&SgImageContent>, desc: &SgImageDesc) -> Result<(), SgError> {
+    if let Some(native) = desc.native {
+        native.check_backend()?;
+    }
+
+    content.map_or(Ok(()), |content| Ok(content.validate(desc)?))
 }
 
 pub fn sg_append_buffer<T>(buf: SgBuffer, content: &T, content_size: i32) -> i32 {
-    unsafe {
+    let offset = unsafe {
         let ptr = content as *const T;
-        ffi::sg_append_buffer(buf, ptr as *const c_void, content_size)
-    }
+        ffi::sg_append_buffer(buf, &ffi::SgRange { ptr: ptr as *const c_void, size: content_size as usize })
+    };
+
+    crate::log::slog_resume_panic();
+    offset
 }
 
 pub fn sg_query_buffer_overflow(buf: SgBuffer) -> bool {
@@ -1783,6 +3183,135 @@ pub fn sg_query_buffer_overflow(buf: SgBuffer) -> bool {
     }
 }
 
+/*
+    typed buffer data
+*/
+
+/// Element types of index buffers.
+pub trait SgIndex: Pod {
+    const INDEX_TYPE: SgIndexType;
+}
+
+impl SgIndex for u16 {
+    const INDEX_TYPE: SgIndexType = SgIndexType::UInt16;
+}
+
+impl SgIndex for u32 {
+    const INDEX_TYPE: SgIndexType = SgIndexType::UInt32;
+}
+
+/// Returned when data doesn't fit into a buffer.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SgBufferOverflow {
+    pub buffer: SgBuffer,
+    /// Size of the data, in bytes.
+    pub size: usize,
+    /// Size of the buffer, in bytes.
+    pub capacity: usize,
+}
+
+impl fmt::Display for SgBufferOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes don't fit into {:?} of {} bytes", self.size, self.buffer, self.capacity)
+    }
+}
+
+impl std::error::Error for SgBufferOverflow {}
+
+/// Creates a buffer with `data` as content. The size is derived from `data`,
+/// `desc.size` must be either `0` or the same.
+///
+/// Panics if the sizes don't match, or if `desc.native` doesn't belong to the active
+/// backend. `sg_try_make_buffer_slice()` returns an error instead.
+pub fn sg_make_buffer_slice<T: Pod>(data: &[T], desc: &SgBufferDesc) -> SgBuffer {
+    let bytes: &[u8] = bytemuck::cast_slice(data);
+
+    expect_valid("sg_make_buffer_slice", check_buffer_slice(bytes, desc), "sg_try_make_buffer_slice()");
+
+    let mut strings = SAppStrings::default();
+
+    let mut buf = ffi::SgBufferDesc::make(SG_BUFFER_CONTENT_NONE, desc, &mut strings);
+    buf.set_data(bytes);
+
+    let id = unsafe {
+        ffi::sg_make_buffer(&buf)
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+fn check_buffer_slice(bytes: &[u8], desc: &SgBufferDesc) -> Result<(), SgError> {
+    if desc.size != 0 && desc.size != bytes.len() {
+        return Err(SgError::BufferSize { size: desc.size, data_size: bytes.len() });
+    }
+
+    check_native_buffer(desc)
+}
+
+/// Creates an index buffer with `indices` as content. The size is derived from `indices`,
+/// and `desc.buffer_type` is ignored.
+///
+/// The matching `SgPipelineDesc::index_type` is `I::INDEX_TYPE`. Panics like
+/// `sg_make_buffer_slice()`.
+pub fn sg_make_index_buffer<I: SgIndex>(indices: &[I], desc: &SgBufferDesc) -> SgBuffer {
+    sg_make_buffer_slice(indices, &SgBufferDesc {
+        buffer_type: SgBufferType::IndexBuffer,
+        label: desc.label,
+        native: desc.native,
+        ..*desc
+    })
+}
+
+/// Replaces the content of a dynamic or stream buffer with `data`.
+///
+/// Fails if `data` is larger than the buffer.
+pub fn sg_update_buffer_slice<T: Pod>(buf: SgBuffer, data: &[T]) -> Result<(), SgBufferOverflow> {
+    let bytes: &[u8] = bytemuck::cast_slice(data);
+
+    let capacity = sg_query_buffer_desc(buf).size;
+    if bytes.len() > capacity {
+        return Err(SgBufferOverflow {
+            buffer: buf,
+            size: bytes.len(),
+            capacity,
+        });
+    }
+
+    unsafe {
+        ffi::sg_update_buffer(buf, &ffi::SgRange::from_slice(bytes));
+    }
+
+    crate::log::slog_resume_panic();
+    Ok(())
+}
+
+/// Appends `data` to a dynamic or stream buffer, and returns its byte offset in the buffer.
+///
+/// Fails if `data` doesn't fit into the rest of the buffer for this frame.
+pub fn sg_append_buffer_slice<T: Pod>(buf: SgBuffer, data: &[T]) -> Result<i32, SgBufferOverflow> {
+    let bytes: &[u8] = bytemuck::cast_slice(data);
+
+    let will_overflow = unsafe {
+        ffi::sg_query_buffer_will_overflow(buf, bytes.len())
+    };
+
+    if will_overflow {
+        return Err(SgBufferOverflow {
+            buffer: buf,
+            size: bytes.len(),
+            capacity: sg_query_buffer_desc(buf).size,
+        });
+    }
+
+    let offset = unsafe {
+        ffi::sg_append_buffer(buf, &ffi::SgRange::from_slice(bytes))
+    };
+
+    crate::log::slog_resume_panic();
+    Ok(offset)
+}
+
 pub fn sg_query_buffer_state(buf: SgBuffer) -> SgResourceState {
     unsafe {
         ffi::sg_query_buffer_state(buf)
@@ -1813,19 +3342,167 @@ pub fn sg_query_pass_state(pass: SgPass) -> SgResourceState {
     }
 }
 
+pub fn sg_query_buffer_info(buf: SgBuffer) -> SgBufferInfo {
+    unsafe {
+        ffi::sg_query_buffer_info(buf)
+    }
+}
+
+pub fn sg_query_image_info(img: SgImage) -> SgImageInfo {
+    unsafe {
+        ffi::sg_query_image_info(img)
+    }
+}
+
+pub fn sg_query_shader_info(shd: SgShader) -> SgShaderInfo {
+    unsafe {
+        ffi::sg_query_shader_info(shd)
+    }
+}
+
+pub fn sg_query_pipeline_info(pip: SgPipeline) -> SgPipelineInfo {
+    unsafe {
+        ffi::sg_query_pipeline_info(pip)
+    }
+}
+
+pub fn sg_query_pass_info(pass: SgPass) -> SgPassInfo {
+    unsafe {
+        ffi::sg_query_pass_info(pass)
+    }
+}
+
+/// Returns the description a buffer was created with, or a zeroed description if the
+/// handle is invalid. Initial content is not included.
+pub fn sg_query_buffer_desc(buf: SgBuffer) -> SgBufferDesc<'static> {
+    unsafe {
+        ffi::sg_query_buffer_desc(buf).to_desc()
+    }
+}
+
+/// Returns the description an image was created with, or a zeroed description if the
+/// handle is invalid. Initial content is not included.
+pub fn sg_query_image_desc(img: SgImage) -> SgImageDesc<'static> {
+    unsafe {
+        ffi::sg_query_image_desc(img).to_desc()
+    }
+}
+
+/// Returns a partial description of a shader: uniform block sizes and image types.
+///
+/// sokol_gfx doesn't keep sources, names or uniform members around.
+pub fn sg_query_shader_desc(shd: SgShader) -> SgShaderDesc<'static> {
+    unsafe {
+        ffi::sg_query_shader_desc(shd).to_desc(None)
+    }
+}
+
+pub fn sg_query_pipeline_desc(pip: SgPipeline) -> SgPipelineDesc<'static> {
+    unsafe {
+        ffi::sg_query_pipeline_desc(pip).to_desc()
+    }
+}
+
+pub fn sg_query_pass_desc(pass: SgPass) -> SgPassDesc<'static> {
+    unsafe {
+        ffi::sg_query_pass_desc(pass).to_desc()
+    }
+}
+
+/// Returns `desc` with all default values filled in, like `sg_make_buffer()` would.
+pub fn sg_query_buffer_defaults<'a>(desc: &SgBufferDesc<'a>) -> SgBufferDesc<'a> {
+    let mut strings = SAppStrings::default();
+
+    let defaults = unsafe {
+        ffi::sg_query_buffer_defaults(&ffi::SgBufferDesc::make(SG_BUFFER_CONTENT_NONE, desc, &mut strings))
+    };
+
+    SgBufferDesc {
+        label: desc.label,
+        native: desc.native,
+        ..defaults.to_desc()
+    }
+}
+
+/// Returns `desc` with all default values filled in, like `sg_make_image()` would.
+pub fn sg_query_image_defaults<'a>(desc: &SgImageDesc<'a>) -> SgImageDesc<'a> {
+    let mut strings = SAppStrings::default();
+
+    let defaults = unsafe {
+        ffi::sg_query_image_defaults(&ffi::SgImageDesc::make(SG_IMAGE_CONTENT_NONE, desc, &mut strings))
+    };
+
+    SgImageDesc {
+        label: desc.label,
+        native: desc.native,
+        ..defaults.to_desc()
+    }
+}
+
+/// Returns `desc` with all default values filled in, like `sg_make_shader()` would.
+pub fn sg_query_shader_defaults<'a>(desc: &SgShaderDesc<'a>) -> SgShaderDesc<'a> {
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_query_shader_defaults(&ffi::SgShaderDesc::make(desc, &mut strings)).to_desc(Some(desc))
+    }
+}
+
+/// Returns `desc` with all default values filled in, like `sg_make_pipeline()` would.
+pub fn sg_query_pipeline_defaults<'a>(desc: &SgPipelineDesc<'a>) -> SgPipelineDesc<'a> {
+    let mut strings = SAppStrings::default();
+
+    let defaults = unsafe {
+        ffi::sg_query_pipeline_defaults(&ffi::SgPipelineDesc::make(desc, &mut strings))
+    };
+
+    SgPipelineDesc {
+        label: desc.label,
+        ..defaults.to_desc()
+    }
+}
+
+/// Returns `desc` with all default values filled in, like `sg_make_pass()` would.
+pub fn sg_query_pass_defaults<'a>(desc: &SgPassDesc<'a>) -> SgPassDesc<'a> {
+    let mut strings = SAppStrings::default();
+
+    let defaults = unsafe {
+        ffi::sg_query_pass_defaults(&ffi::SgPassDesc::make(desc, &mut strings))
+    };
+
+    SgPassDesc {
+        label: desc.label,
+        ..defaults.to_desc()
+    }
+}
+
+/// Begins a pass which renders to the default framebuffer.
+///
+/// Panics if `pass_action` is invalid, see `SgPassAction::validate()`.
 pub fn sg_begin_default_pass(pass_action: &SgPassAction, width: i32, height: i32) {
+    expect_valid("sg_begin_default_pass", pass_action.validate(), "SgPassAction::validate()");
+
     let action = ffi::SgPassAction::make(pass_action);
     unsafe {
         ffi::sg_begin_default_pass(&action, width, height);
     }
+
+    crate::log::slog_resume_panic();
 }
 
+/// Begins a pass which renders to the attachments of `pass`.
+///
+/// Panics if `pass_action` is invalid, see `SgPassAction::validate()`.
 pub fn sg_begin_pass(pass: SgPass,
                      pass_action: &SgPassAction) {
+    expect_valid("sg_begin_pass", pass_action.validate(), "SgPassAction::validate()");
+
     let action = ffi::SgPassAction::make(pass_action);
     unsafe {
         ffi::sg_begin_pass(pass, &action);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_apply_viewport(x: i32, y: i32,
@@ -1834,6 +3511,8 @@ pub fn sg_apply_viewport(x: i32, y: i32,
     unsafe {
         ffi::sg_apply_viewport(x, y, width, height, origin_top_left);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_apply_scissor_rect(x: i32, y: i32,
@@ -1842,18 +3521,30 @@ pub fn sg_apply_scissor_rect(x: i32, y: i32,
     unsafe {
         ffi::sg_apply_scissor_rect(x, y, width, height, origin_top_left);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_apply_pipeline(pip: SgPipeline) {
     unsafe {
         ffi::sg_apply_pipeline(pip);
     }
+
+    crate::log::slog_resume_panic();
 }
 
+/// Applies the resource bindings for the following draw calls.
+///
+/// Panics if `bindings` is invalid, see `SgBindings::validate()`.
+/// `sg_try_apply_bindings()` returns an error instead.
 pub fn sg_apply_bindings(bindings: &SgBindings) {
+    expect_valid("sg_apply_bindings", bindings.validate(), "sg_try_apply_bindings()");
+
     unsafe {
         ffi::sg_apply_bindings(&ffi::SgBindings::make(bindings));
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_apply_uniforms<T>(stage: SgShaderStage,
@@ -1871,6 +3562,8 @@ pub fn sg_apply_uniforms<T>(stage: SgShaderStage,
                                ub_index,
                                &range as *const ffi::SgRange);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_draw(base_element: i32,
@@ -1879,16 +3572,134 @@ pub fn sg_draw(base_element: i32,
     unsafe {
         ffi::sg_draw(base_element, num_elements, num_instances);
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_end_pass() {
     unsafe {
         ffi::sg_end_pass();
     }
+
+    crate::log::slog_resume_panic();
 }
 
 pub fn sg_commit() {
     unsafe {
         ffi::sg_commit();
     }
+
+    encoder::sg_encoder_reset();
+    crate::log::slog_resume_panic();
+}
+
+/// Starts a named group of commands, shown in frame captures of native graphics debuggers.
+pub fn sg_push_debug_group(name: &str) {
+    let mut strings = SAppStrings::default();
+
+    unsafe {
+        ffi::sg_push_debug_group(strings.add(name));
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+pub fn sg_pop_debug_group() {
+    unsafe {
+        ffi::sg_pop_debug_group();
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/*
+    commit listeners
+*/
+
+type SgCommitCallback = Box<dyn FnMut()>;
+
+/// A commit listener added with `sg_add_commit_listener()`.
+///
+/// The listener stays registered until this handle is dropped, or passed to
+/// `sg_remove_commit_listener()`, which also drops the closure. Handles which outlive
+/// `sg_shutdown()` only drop their closure.
+#[must_use = "the commit listener is removed when the handle is dropped"]
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct SgCommitListener {
+    callback: *mut SgCommitCallback,
+    generation: usize,
+    registered: bool,
+}
+
+impl SgCommitListener {
+    fn unregister(&mut self) -> bool {
+        if !self.registered {
+            return false;
+        }
+
+        self.registered = false;
+
+        if !sg_isvalid() || self.generation != owned::sg_setup_generation() {
+            return false;
+        }
+
+        unsafe {
+            ffi::sg_remove_commit_listener(ffi::SgCommitListener::make(self))
+        }
+    }
+}
+
+impl Drop for SgCommitListener {
+    fn drop(&mut self) {
+        self.unregister();
+
+        unsafe {
+            drop(Box::from_raw(self.callback));
+        }
+    }
+}
+
+extern fn sg_commit_listener_func(user_data: *mut c_void) {
+    let callback = unsafe {
+        &mut *(user_data as *mut SgCommitCallback)
+    };
+
+    // resumed at the end of `sg_commit()`
+    crate::log::slog_catch_panic(callback);
+}
+
+/// Registers a closure to be called at the end of each `sg_commit()`.
+///
+/// Returns `None` if `SgDesc::max_commit_listeners` listeners are registered already.
+/// The closure must not add or remove commit listeners itself. If it panics, the panic
+/// is resumed once `sg_commit()` returns.
+pub fn sg_add_commit_listener<F>(callback: F) -> Option<SgCommitListener>
+    where F: FnMut() + 'static {
+    let callback: SgCommitCallback = Box::new(callback);
+
+    let mut listener = SgCommitListener {
+        callback: Box::into_raw(Box::new(callback)),
+        generation: owned::sg_setup_generation(),
+        registered: false,
+    };
+
+    listener.registered = unsafe {
+        ffi::sg_add_commit_listener(ffi::SgCommitListener::make(&listener))
+    };
+
+    crate::log::slog_resume_panic();
+
+    if listener.registered {
+        Some(listener)
+    } else {
+        None
+    }
+}
+
+/// Unregisters a commit listener, and drops its closure.
+///
+/// Returns `false` if the listener was not registered, e.g. because sokol_gfx has
+/// been shut down and set up again in between.
+pub fn sg_remove_commit_listener(mut listener: SgCommitListener) -> bool {
+    listener.unregister()
 }
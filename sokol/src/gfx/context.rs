@@ -0,0 +1,211 @@
+//! Render contexts
+//!
+//! sokol_gfx keeps a separate set of resources per context. `sg_setup()` creates and
+//! activates the first context, further ones are created with `sg_setup_context()`, e.g.
+//! one per GL context of an editor with multiple viewports:
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! // the GL context of the viewport must be current
+//! viewport.gl_context.make_current();
+//! viewport.sg_context = sg_setup_context();
+//!
+//! // resources are created in, and only usable with, the active context
+//! viewport.pipeline = sg_make_pipeline(&pipeline_desc);
+//!
+//! // later, for each frame
+//! viewport.gl_context.make_current();
+//! sg_activate_context(viewport.sg_context);
+//!
+//! // and when the viewport is closed
+//! sg_discard_context(viewport.sg_context);
+//! ```
+//!
+//! `SgContextDesc` describes the render target of the default pass, and how sokol_gfx
+//! obtains the device and views of the D3D11, Metal and WebGPU backends. It's passed to
+//! `sg_setup()` with `SgDesc::context`, which makes sokol_gfx usable without `sapp_run()`,
+//! in a window and event loop owned by another library:
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! // the GL context of the window must be current
+//! sg_setup(&SgDesc {
+//!     context: Some(SgContextDesc {
+//!         color_format: SgPixelFormat::RGBA8,
+//!         depth_format: SgPixelFormat::DepthStencil,
+//!         sample_count: 1,
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! });
+//! ```
+
+use std::os::raw::c_void;
+use std::ptr::{null, null_mut};
+
+use super::*;
+
+/// Returns a pointer to a backend object, e.g. the current `ID3D11RenderTargetView`.
+pub type SgContextCallback = extern fn() -> *const c_void;
+
+/// Same as `SgContextCallback`, but called with the `user_data` of the context desc.
+pub type SgContextUserDataCallback = extern fn(*mut c_void) -> *const c_void;
+
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgGLContextDesc {
+    pub force_gles2: bool,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct SgMetalContextDesc {
+    /// `MTLDevice`
+    pub device: *const c_void,
+    /// Returns the `MTLRenderPassDescriptor` of the current frame.
+    pub renderpass_descriptor_cb: Option<SgContextCallback>,
+    pub renderpass_descriptor_userdata_cb: Option<SgContextUserDataCallback>,
+    /// Returns the `MTLDrawable` of the current frame.
+    pub drawable_cb: Option<SgContextCallback>,
+    pub drawable_userdata_cb: Option<SgContextUserDataCallback>,
+    pub user_data: *mut c_void,
+}
+
+impl Default for SgMetalContextDesc {
+    fn default() -> Self {
+        SgMetalContextDesc {
+            device: null(),
+            renderpass_descriptor_cb: None,
+            renderpass_descriptor_userdata_cb: None,
+            drawable_cb: None,
+            drawable_userdata_cb: None,
+            user_data: null_mut(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct SgD3D11ContextDesc {
+    /// `ID3D11Device`
+    pub device: *const c_void,
+    /// `ID3D11DeviceContext`
+    pub device_context: *const c_void,
+    /// Returns the `ID3D11RenderTargetView` of the current frame.
+    pub render_target_view_cb: Option<SgContextCallback>,
+    pub render_target_view_userdata_cb: Option<SgContextUserDataCallback>,
+    /// Returns the `ID3D11DepthStencilView` of the current frame.
+    pub depth_stencil_view_cb: Option<SgContextCallback>,
+    pub depth_stencil_view_userdata_cb: Option<SgContextUserDataCallback>,
+    pub user_data: *mut c_void,
+}
+
+impl Default for SgD3D11ContextDesc {
+    fn default() -> Self {
+        SgD3D11ContextDesc {
+            device: null(),
+            device_context: null(),
+            render_target_view_cb: None,
+            render_target_view_userdata_cb: None,
+            depth_stencil_view_cb: None,
+            depth_stencil_view_userdata_cb: None,
+            user_data: null_mut(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct SgWGPUContextDesc {
+    /// `WGPUDevice`
+    pub device: *const c_void,
+    /// Returns the `WGPUTextureView` to render to.
+    pub render_view_cb: Option<SgContextCallback>,
+    pub render_view_userdata_cb: Option<SgContextUserDataCallback>,
+    /// Returns the `WGPUTextureView` to resolve MSAA rendering to.
+    pub resolve_view_cb: Option<SgContextCallback>,
+    pub resolve_view_userdata_cb: Option<SgContextUserDataCallback>,
+    /// Returns the depth-stencil `WGPUTextureView`.
+    pub depth_stencil_view_cb: Option<SgContextCallback>,
+    pub depth_stencil_view_userdata_cb: Option<SgContextUserDataCallback>,
+    pub user_data: *mut c_void,
+}
+
+impl Default for SgWGPUContextDesc {
+    fn default() -> Self {
+        SgWGPUContextDesc {
+            device: null(),
+            render_view_cb: None,
+            render_view_userdata_cb: None,
+            resolve_view_cb: None,
+            resolve_view_userdata_cb: None,
+            depth_stencil_view_cb: None,
+            depth_stencil_view_userdata_cb: None,
+            user_data: null_mut(),
+        }
+    }
+}
+
+/// The render target of the default pass, and the backend specific objects sokol_gfx
+/// renders with.
+///
+/// Only the desc of the active backend is used. The formats and sample count are
+/// defaults for pipelines and passes, and must match the framebuffer of the window.
+#[repr(C)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgContextDesc {
+    pub color_format: SgPixelFormat,
+    pub depth_format: SgPixelFormat,
+    pub sample_count: i32,
+    pub gl: SgGLContextDesc,
+    pub metal: SgMetalContextDesc,
+    pub d3d11: SgD3D11ContextDesc,
+    pub wgpu: SgWGPUContextDesc,
+}
+
+impl SgResource for SgContext {
+    /// Discards the context and all resources created in it, see `sg_discard_context()`.
+    fn destroy(self) {
+        sg_discard_context(self);
+    }
+}
+
+pub type SgOwnedContext = SgOwned<SgContext>;
+
+/// Creates a new context, and makes it the active one.
+///
+/// The new context uses the `SgContextDesc` passed to `sg_setup()`. With the GL backends,
+/// the GL context it belongs to must be current.
+pub fn sg_setup_context() -> SgContext {
+    let id = unsafe {
+        ffi::sg_setup_context()
+    };
+
+    crate::log::slog_resume_panic();
+    id
+}
+
+/// Makes `ctx_id` the active context. Resources are created in, and can only be used
+/// with, the active context.
+///
+/// With the GL backends, the GL context it belongs to must be made current first.
+pub fn sg_activate_context(ctx_id: SgContext) {
+    unsafe {
+        ffi::sg_activate_context(ctx_id);
+    }
+
+    crate::log::slog_resume_panic();
+}
+
+/// Destroys all resources created in `ctx_id`, and the context itself.
+///
+/// With the GL backends, the GL context it belongs to must be current.
+pub fn sg_discard_context(ctx_id: SgContext) {
+    unsafe {
+        ffi::sg_discard_context(ctx_id);
+    }
+
+    crate::log::slog_resume_panic();
+}
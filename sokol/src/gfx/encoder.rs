@@ -0,0 +1,159 @@
+//! Pass encoders
+//!
+//! `SgPassEncoder` begins a render pass, and ends it when dropped. Bindings, uniforms and
+//! draw calls are only available on the `SgPipelineEncoder` returned by `apply_pipeline()`,
+//! so calling them outside of a pass, or before a pipeline is applied, doesn't compile:
+//!
+//! ```no_run
+//! use sokol::gfx::*;
+//!
+//! # fn frame(pass_action: &SgPassAction, pipeline: SgPipeline, bindings: &SgBindings, mvp: &[f32; 16]) {
+//! {
+//!     let mut pass = SgPassEncoder::begin_default(pass_action, 640, 480);
+//!
+//!     let mut pip = pass.apply_pipeline(pipeline);
+//!     pip.apply_bindings(bindings);
+//!     pip.apply_uniforms(SgShaderStage::Vertex, 0, mvp, 64);
+//!     pip.draw(0, 36, 1);
+//!
+//!     // `sg_end_pass()` is called here
+//! }
+//!
+//! sg_commit();
+//! # }
+//! ```
+//!
+//! Drawing without a pipeline:
+//!
+//! ```compile_fail,E0599
+//! use sokol::gfx::*;
+//!
+//! # fn frame(pass_action: &SgPassAction) {
+//! let mut pass = SgPassEncoder::begin_default(pass_action, 640, 480);
+//! pass.draw(0, 36, 1);
+//! # }
+//! ```
+//!
+//! Drawing after the pass has ended:
+//!
+//! ```compile_fail,E0505
+//! use sokol::gfx::*;
+//!
+//! # fn frame(pass_action: &SgPassAction, pipeline: SgPipeline) {
+//! let mut pass = SgPassEncoder::begin_default(pass_action, 640, 480);
+//! let mut pip = pass.apply_pipeline(pipeline);
+//! pass.end();
+//! pip.draw(0, 36, 1);
+//! # }
+//! ```
+//!
+//! Passes can't be nested. Beginning a pass while another `SgPassEncoder` is alive panics.
+//! An encoder which is never dropped, e.g. with `mem::forget()`, stops blocking new passes
+//! at the next `sg_commit()`.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use super::*;
+
+thread_local! {
+    static IN_PASS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A render pass, which ends when the encoder is dropped.
+///
+/// Like sokol_gfx itself, it can't be sent to other threads.
+pub struct SgPassEncoder {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl SgPassEncoder {
+    /// Begins a pass rendering to the default framebuffer, see `sg_begin_default_pass()`.
+    pub fn begin_default(pass_action: &SgPassAction, width: i32, height: i32) -> Self {
+        let encoder = SgPassEncoder::new();
+        sg_begin_default_pass(pass_action, width, height);
+        encoder
+    }
+
+    /// Begins an offscreen pass, see `sg_begin_pass()`.
+    pub fn begin(pass: SgPass, pass_action: &SgPassAction) -> Self {
+        let encoder = SgPassEncoder::new();
+        sg_begin_pass(pass, pass_action);
+        encoder
+    }
+
+    fn new() -> Self {
+        IN_PASS.with(|in_pass| {
+            assert!(!in_pass.replace(true), "a pass can't begin while another pass is active");
+        });
+
+        SgPassEncoder {
+            _not_send: PhantomData,
+        }
+    }
+
+    pub fn apply_viewport(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {
+        sg_apply_viewport(x, y, width, height, origin_top_left);
+    }
+
+    pub fn apply_scissor_rect(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {
+        sg_apply_scissor_rect(x, y, width, height, origin_top_left);
+    }
+
+    /// Applies a pipeline, and returns the encoder for the bindings, uniforms and draw
+    /// calls using it. To switch to another pipeline, call this again.
+    pub fn apply_pipeline(&mut self, pip: SgPipeline) -> SgPipelineEncoder<'_> {
+        sg_apply_pipeline(pip);
+
+        SgPipelineEncoder {
+            pass: self,
+        }
+    }
+
+    /// Ends the pass. Same as dropping the encoder.
+    pub fn end(self) {}
+}
+
+/// Forgets a pass whose encoder was never dropped, on `sg_commit()` and `sg_shutdown()`.
+pub(super) fn sg_encoder_reset() {
+    IN_PASS.with(|in_pass| in_pass.set(false));
+}
+
+impl Drop for SgPassEncoder {
+    fn drop(&mut self) {
+        sg_end_pass();
+
+        IN_PASS.with(|in_pass| in_pass.set(false));
+    }
+}
+
+/// A pipeline applied in a pass, see `SgPassEncoder::apply_pipeline()`.
+pub struct SgPipelineEncoder<'a> {
+    pass: &'a mut SgPassEncoder,
+}
+
+impl SgPipelineEncoder<'_> {
+    pub fn apply_viewport(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {
+        self.pass.apply_viewport(x, y, width, height, origin_top_left);
+    }
+
+    pub fn apply_scissor_rect(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {
+        self.pass.apply_scissor_rect(x, y, width, height, origin_top_left);
+    }
+
+    pub fn apply_bindings(&mut self, bindings: &SgBindings) {
+        sg_apply_bindings(bindings);
+    }
+
+    pub fn apply_uniforms<T>(&mut self, stage: SgShaderStage, ub_index: i32, data: &T, num_bytes: i32) {
+        sg_apply_uniforms(stage, ub_index, data, num_bytes);
+    }
+
+    pub fn apply_uniform_block<T: SgUniformBlock>(&mut self, stage: SgShaderStage, ub_index: i32, data: &T) {
+        sg_apply_uniform_block(stage, ub_index, data);
+    }
+
+    pub fn draw(&mut self, base_element: i32, num_elements: i32, num_instances: i32) {
+        sg_draw(base_element, num_elements, num_instances);
+    }
+}
@@ -0,0 +1,330 @@
+//! Image content
+//!
+//! `SgImageContent` collects the initial content of an image, or the data passed to
+//! `sg_update_image()`, as borrowed slices per cube face and mipmap level.
+//!
+//! - 2D images have one slice per mip level.
+//! - Cube images have one slice per face and mip level.
+//! - 3D and array images have one slice per mip level, which contains all of
+//!   the image's slices (or layers) tightly packed.
+//!
+//! ```no_run
+//! use sokol::gfx::*;
+//!
+//! let pixels = [0xffu8; 4 * 4 * 4];
+//!
+//! let img = sg_make_image(Some(&SgImageContent::new().mip(0, &pixels)), &SgImageDesc {
+//!     width: 4,
+//!     height: 4,
+//!     pixel_format: SgPixelFormat::RGBA8,
+//!     ..Default::default()
+//! });
+//! ```
+
+use std::fmt;
+
+use bytemuck::Pod;
+
+use super::*;
+
+#[derive(Clone, Default, Debug)]
+pub struct SgImageContent<'a> {
+    subimages: [[Option<&'a [u8]>; SG_MAX_MIPMAPS]; SG_CUBEFACE_NUM],
+}
+
+impl<'a> SgImageContent<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the data of a mip level of a 2D, 3D or array image.
+    pub fn mip<T: Pod>(self, mip: usize, data: &'a [T]) -> Self {
+        self.face_mip(SgCubeFace::PosX, mip, data)
+    }
+
+    /// Sets the data of a mip level of a cube image face.
+    pub fn face_mip<T: Pod>(mut self, face: SgCubeFace, mip: usize, data: &'a [T]) -> Self {
+        assert!((face as usize) < SG_CUBEFACE_NUM, "invalid cube face {:?}", face);
+        assert!(mip < SG_MAX_MIPMAPS, "mip level {} out of range", mip);
+
+        self.subimages[face as usize][mip] = Some(bytemuck::cast_slice(data));
+        self
+    }
+
+    pub(super) fn subimage(&self, face: usize, mip: usize) -> Option<&'a [u8]> {
+        self.subimages[face][mip]
+    }
+
+    /// Checks that the content matches an image, i.e. that data is given for each face
+    /// and mip level of the image, with the size expected for its pixel format.
+    pub fn validate(&self, desc: &SgImageDesc) -> Result<(), SgImageContentError> {
+        let image_type = match desc.image_type {
+            SgImageType::_Default => SgImageType::Texture2D,
+            image_type => image_type,
+        };
+
+        let pixel_format = match desc.pixel_format {
+            SgPixelFormat::_Default => SgPixelFormat::RGBA8,
+            pixel_format => pixel_format,
+        };
+
+        let num_faces = if image_type == SgImageType::TextureCube { SG_CUBEFACE_NUM } else { 1 };
+        let num_mips = desc.num_mipmaps.max(1) as usize;
+        let num_slices = desc.num_slices.max(1) as usize;
+
+        for face in 0..SG_CUBEFACE_NUM {
+            for mip in 0..SG_MAX_MIPMAPS {
+                let data = self.subimages[face][mip];
+
+                if face >= num_faces || mip >= num_mips {
+                    if data.is_some() {
+                        return Err(SgImageContentError::Unexpected { face, mip });
+                    }
+                    continue;
+                }
+
+                let data = data.ok_or(SgImageContentError::Missing { face, mip })?;
+
+                let width = (desc.width as usize >> mip).max(1);
+                let height = (desc.height as usize >> mip).max(1);
+                let slices = match image_type {
+                    SgImageType::Texture3D => (num_slices >> mip).max(1),
+                    SgImageType::TextureArray => num_slices,
+                    _ => 1,
+                };
+
+                let expected = pixel_format.surface_pitch(width, height) * slices;
+
+                if data.len() != expected {
+                    return Err(SgImageContentError::Size {
+                        face,
+                        mip,
+                        expected,
+                        actual: data.len(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SgImageContentError {
+    /// No data given for a cube face or mip level of the image.
+    Missing { face: usize, mip: usize },
+    /// Data given for a cube face or mip level the image doesn't have.
+    Unexpected { face: usize, mip: usize },
+    /// The data size of a cube face or mip level doesn't match the image.
+    Size { face: usize, mip: usize, expected: usize, actual: usize },
+}
+
+impl fmt::Display for SgImageContentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SgImageContentError::Missing { face, mip } =>
+                write!(f, "missing image data for face {}, mip level {}", face, mip),
+            SgImageContentError::Unexpected { face, mip } =>
+                write!(f, "unexpected image data for face {}, mip level {}", face, mip),
+            SgImageContentError::Size { face, mip, expected, actual } =>
+                write!(f, "image data for face {}, mip level {} has {} bytes, expected {}", face, mip, actual, expected),
+        }
+    }
+}
+
+impl std::error::Error for SgImageContentError {}
+
+impl SgPixelFormat {
+    /// Returns the size of a pixel in bytes, or `None` for compressed formats.
+    pub fn bytes_per_pixel(self) -> Option<usize> {
+        use SgPixelFormat::*;
+
+        match self {
+            R8 | R8SN | R8UI | R8SI => Some(1),
+
+            R16 | R16SN | R16UI | R16SI | R16F |
+            RG8 | RG8SN | RG8UI | RG8SI => Some(2),
+
+            R32UI | R32SI | R32F |
+            RG16 | RG16SN | RG16UI | RG16SI | RG16F |
+            RGBA8 | SRGB8A8 | RGBA8SN | RGBA8UI | RGBA8SI | BGRA8 |
+            RGB10A2 | RG11B10F | RGB9E5 |
+            Depth | DepthStencil => Some(4),
+
+            RG32UI | RG32SI | RG32F |
+            RGBA16 | RGBA16SN | RGBA16UI | RGBA16SI | RGBA16F => Some(8),
+
+            RGBA32UI | RGBA32SI | RGBA32F => Some(16),
+
+            _ => Option::None,
+        }
+    }
+
+    /// Returns the number of bytes of a row of pixels, or of blocks for compressed formats.
+    pub fn row_pitch(self, width: usize) -> usize {
+        use SgPixelFormat::*;
+
+        match self {
+            BC1_RGBA | BC4_R | BC4_RSN | ETC2_RGB8 | ETC2_RGB8A1 =>
+                (width.div_ceil(4) * 8).max(8),
+            BC2_RGBA | BC3_RGBA | BC5_RG | BC5_RGSN | BC6H_RGBF | BC6H_RGBUF | BC7_RGBA |
+            ETC2_RGBA8 | ETC2_RG11 | ETC2_RG11SN =>
+                (width.div_ceil(4) * 16).max(16),
+            PVRTC_RGB_4BPP | PVRTC_RGBA_4BPP => (width.max(8) * 4).div_ceil(8),
+            PVRTC_RGB_2BPP | PVRTC_RGBA_2BPP => (width.max(16) * 2).div_ceil(8),
+            _ => width * self.bytes_per_pixel().unwrap_or(0),
+        }
+    }
+
+    /// Returns the number of rows of pixels, or of blocks for compressed formats.
+    pub fn num_rows(self, height: usize) -> usize {
+        use SgPixelFormat::*;
+
+        let num_rows = match self {
+            BC1_RGBA | BC2_RGBA | BC3_RGBA | BC4_R | BC4_RSN | BC5_RG | BC5_RGSN |
+            BC6H_RGBF | BC6H_RGBUF | BC7_RGBA |
+            ETC2_RGB8 | ETC2_RGB8A1 | ETC2_RGBA8 | ETC2_RG11 | ETC2_RG11SN =>
+                height.div_ceil(4),
+            PVRTC_RGB_4BPP | PVRTC_RGBA_4BPP | PVRTC_RGB_2BPP | PVRTC_RGBA_2BPP =>
+                height.max(8).div_ceil(8) * 8,
+            _ => height,
+        };
+
+        num_rows.max(1)
+    }
+
+    /// Returns the number of bytes of a 2D surface, i.e. of one slice of an image.
+    pub fn surface_pitch(self, width: usize, height: usize) -> usize {
+        self.row_pitch(width) * self.num_rows(height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desc(image_type: SgImageType, pixel_format: SgPixelFormat, width: i32, height: i32) -> SgImageDesc<'static> {
+        SgImageDesc {
+            image_type,
+            pixel_format,
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn missing() {
+        let desc = SgImageDesc { num_mipmaps: 2, ..desc(SgImageType::Texture2D, SgPixelFormat::RGBA8, 2, 2) };
+        let pixels = [0u8; 2 * 2 * 4];
+
+        assert_eq!(SgImageContent::new().validate(&desc), Err(SgImageContentError::Missing { face: 0, mip: 0 }));
+        assert_eq!(SgImageContent::new().mip(0, &pixels).validate(&desc),
+                   Err(SgImageContentError::Missing { face: 0, mip: 1 }));
+    }
+
+    #[test]
+    fn unexpected() {
+        let desc = desc(SgImageType::Texture2D, SgPixelFormat::RGBA8, 2, 2);
+        let pixels = [0u8; 2 * 2 * 4];
+
+        assert_eq!(SgImageContent::new().mip(0, &pixels).mip(1, &pixels[..4]).validate(&desc),
+                   Err(SgImageContentError::Unexpected { face: 0, mip: 1 }));
+        assert_eq!(SgImageContent::new().mip(0, &pixels).face_mip(SgCubeFace::NegX, 0, &pixels).validate(&desc),
+                   Err(SgImageContentError::Unexpected { face: 1, mip: 0 }));
+    }
+
+    #[test]
+    fn size() {
+        let desc = desc(SgImageType::Texture2D, SgPixelFormat::RGBA8, 3, 2);
+        let pixels = [0u8; 3 * 2 * 4];
+
+        assert_eq!(SgImageContent::new().mip(0, &pixels).validate(&desc), Ok(()));
+        assert_eq!(SgImageContent::new().mip(0, &pixels[1..]).validate(&desc),
+                   Err(SgImageContentError::Size { face: 0, mip: 0, expected: 24, actual: 23 }));
+
+        // pod slices are measured in bytes
+        let pixels = [0u32; 3 * 2];
+        assert_eq!(SgImageContent::new().mip(0, &pixels).validate(&desc), Ok(()));
+    }
+
+    #[test]
+    fn block_compressed() {
+        // 5x5 is 2x2 blocks of 8 bytes, the 2x2 and 1x1 mips still take a whole block
+        let desc1 = SgImageDesc { num_mipmaps: 3, ..desc(SgImageType::Texture2D, SgPixelFormat::BC1_RGBA, 5, 5) };
+        let blocks = [0u8; 2 * 2 * 8];
+        let content = SgImageContent::new().mip(0, &blocks).mip(1, &blocks[..8]).mip(2, &blocks[..8]);
+        assert_eq!(content.validate(&desc1), Ok(()));
+
+        let content = SgImageContent::new().mip(0, &blocks[..24]).mip(1, &blocks[..8]).mip(2, &blocks[..8]);
+        assert_eq!(content.validate(&desc1),
+                   Err(SgImageContentError::Size { face: 0, mip: 0, expected: 32, actual: 24 }));
+
+        // 6x3 is 2x1 blocks of 16 bytes
+        let desc3 = desc(SgImageType::Texture2D, SgPixelFormat::BC3_RGBA, 6, 3);
+        assert_eq!(SgImageContent::new().mip(0, &[0u8; 2 * 16]).validate(&desc3), Ok(()));
+        assert_eq!(SgImageContent::new().mip(0, &[0u8; 6 * 3 * 4]).validate(&desc3),
+                   Err(SgImageContentError::Size { face: 0, mip: 0, expected: 32, actual: 72 }));
+    }
+
+    #[test]
+    fn pvrtc() {
+        // PVRTC surfaces are at least 8x8 (4bpp) or 16x8 (2bpp) pixels
+        let desc4 = desc(SgImageType::Texture2D, SgPixelFormat::PVRTC_RGBA_4BPP, 4, 4);
+        assert_eq!(SgImageContent::new().mip(0, &[0u8; 8 * 8 / 2]).validate(&desc4), Ok(()));
+
+        let desc2 = desc(SgImageType::Texture2D, SgPixelFormat::PVRTC_RGB_2BPP, 4, 4);
+        assert_eq!(SgImageContent::new().mip(0, &[0u8; 16 * 8 / 4]).validate(&desc2), Ok(()));
+
+        let desc4 = desc(SgImageType::Texture2D, SgPixelFormat::PVRTC_RGB_4BPP, 16, 16);
+        assert_eq!(SgImageContent::new().mip(0, &[0u8; 32]).validate(&desc4),
+                   Err(SgImageContentError::Size { face: 0, mip: 0, expected: 128, actual: 32 }));
+    }
+
+    #[test]
+    fn texture_3d() {
+        // the slices of a 3D image are halved per mip level, unlike those of an array image
+        let desc3d = SgImageDesc {
+            num_slices: 4,
+            num_mipmaps: 3,
+            ..desc(SgImageType::Texture3D, SgPixelFormat::RGBA8, 4, 4)
+        };
+        let content = SgImageContent::new()
+            .mip(0, &[0u8; 4 * 4 * 4 * 4])
+            .mip(1, &[0u8; 2 * 2 * 2 * 4])
+            .mip(2, &[0u8; 4]);
+        assert_eq!(content.validate(&desc3d), Ok(()));
+
+        let desc_array = SgImageDesc { image_type: SgImageType::TextureArray, ..desc3d };
+        assert_eq!(content.validate(&desc_array),
+                   Err(SgImageContentError::Size { face: 0, mip: 1, expected: 2 * 2 * 4 * 4, actual: 2 * 2 * 2 * 4 }));
+    }
+
+    #[test]
+    fn cube_faces() {
+        let desc = desc(SgImageType::TextureCube, SgPixelFormat::RGBA8, 2, 2);
+        let pixels = [0u8; 2 * 2 * 4];
+        let faces = [SgCubeFace::PosX, SgCubeFace::NegX, SgCubeFace::PosY,
+                     SgCubeFace::NegY, SgCubeFace::PosZ, SgCubeFace::NegZ];
+
+        let content = faces.iter().fold(SgImageContent::new(), |content, &face| content.face_mip(face, 0, &pixels));
+        assert_eq!(content.validate(&desc), Ok(()));
+
+        let content = faces[..5].iter().fold(SgImageContent::new(), |content, &face| content.face_mip(face, 0, &pixels));
+        assert_eq!(content.validate(&desc), Err(SgImageContentError::Missing { face: 5, mip: 0 }));
+    }
+
+    #[test]
+    fn defaults() {
+        // a default image is a 2D RGBA8 image with one mip level
+        let desc = desc(SgImageType::_Default, SgPixelFormat::_Default, 2, 2);
+        let pixels = [0u8; 2 * 2 * 4];
+
+        assert_eq!(SgImageContent::new().mip(0, &pixels).validate(&desc), Ok(()));
+        assert_eq!(SgImageContent::new().mip(0, &pixels[..4]).validate(&desc),
+                   Err(SgImageContentError::Size { face: 0, mip: 0, expected: 16, actual: 4 }));
+        assert_eq!(SgImageContent::new().mip(0, &pixels).face_mip(SgCubeFace::NegX, 0, &pixels).validate(&desc),
+                   Err(SgImageContentError::Unexpected { face: 1, mip: 0 }));
+    }
+}
@@ -0,0 +1,148 @@
+//! Owned resource handles
+//!
+//! `SgOwned` wraps a resource id, and destroys the resource when it is dropped.
+//! Resources shared by several owners, e.g. a shader used by multiple pipelines,
+//! can be reference-counted with `Rc<SgOwned<_>>`.
+//!
+//! ```no_run
+//! use std::rc::Rc;
+//! use sokol::gfx::*;
+//!
+//! # let shader_desc: SgShaderDesc = unimplemented!();
+//! let shd = Rc::new(SgOwned::new(sg_make_shader(&shader_desc)));
+//!
+//! let pip = SgOwned::new(sg_make_pipeline(&SgPipelineDesc {
+//!     shader: shd.id(),
+//!     ..Default::default()
+//! }));
+//!
+//! // the pipeline is destroyed here, the shader once the last `Rc` is dropped
+//! drop(pip);
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use super::*;
+
+/// A resource id which can be owned by `SgOwned`.
+pub trait SgResource: Copy + fmt::Debug {
+    /// Destroys the resource, see `sg_destroy_buffer()` etc.
+    fn destroy(self);
+}
+
+impl SgResource for SgBuffer {
+    fn destroy(self) {
+        sg_destroy_buffer(self);
+    }
+}
+
+impl SgResource for SgImage {
+    fn destroy(self) {
+        sg_destroy_image(self);
+    }
+}
+
+impl SgResource for SgShader {
+    fn destroy(self) {
+        sg_destroy_shader(self);
+    }
+}
+
+impl SgResource for SgPipeline {
+    fn destroy(self) {
+        sg_destroy_pipeline(self);
+    }
+}
+
+impl SgResource for SgPass {
+    fn destroy(self) {
+        sg_destroy_pass(self);
+    }
+}
+
+/// Owns a sokol_gfx resource, and destroys it when dropped.
+///
+/// Dereferences to the raw id, so it can be used with `SgBindings` and all functions
+/// taking resource ids. Like sokol_gfx itself, it can't be sent to other threads.
+///
+/// Resources which are still owned after `sg_shutdown()` are not destroyed again, also
+/// not after sokol_gfx has been set up again, where their ids may refer to new resources.
+pub struct SgOwned<T: SgResource> {
+    id: T,
+    generation: usize,
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Counts calls to `sg_setup()`, so handles can tell whether their id is from the
+/// current setup.
+static SETUP_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+pub(super) fn sg_next_setup_generation() {
+    SETUP_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(super) fn sg_setup_generation() -> usize {
+    SETUP_GENERATION.load(Ordering::Relaxed)
+}
+
+pub type SgOwnedBuffer = SgOwned<SgBuffer>;
+pub type SgOwnedImage = SgOwned<SgImage>;
+pub type SgOwnedShader = SgOwned<SgShader>;
+pub type SgOwnedPipeline = SgOwned<SgPipeline>;
+pub type SgOwnedPass = SgOwned<SgPass>;
+
+impl<T: SgResource> SgOwned<T> {
+    /// Takes ownership of a resource id.
+    pub fn new(id: T) -> Self {
+        SgOwned {
+            id,
+            generation: sg_setup_generation(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the raw resource id, which stays owned by `self`.
+    pub fn id(&self) -> T {
+        self.id
+    }
+
+    /// Releases ownership, and returns the raw resource id without destroying it.
+    pub fn into_raw(self) -> T {
+        let id = self.id;
+        std::mem::forget(self);
+        id
+    }
+}
+
+impl<T: SgResource + Default> Default for SgOwned<T> {
+    /// An invalid resource id. Destroying it is a no-op.
+    fn default() -> Self {
+        SgOwned::new(T::default())
+    }
+}
+
+impl<T: SgResource> Deref for SgOwned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.id
+    }
+}
+
+impl<T: SgResource> Drop for SgOwned<T> {
+    fn drop(&mut self) {
+        if sg_isvalid() && self.generation == sg_setup_generation() {
+            self.id.destroy();
+        }
+    }
+}
+
+impl<T: SgResource> fmt::Debug for SgOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SgOwned").field(&self.id).finish()
+    }
+}
@@ -0,0 +1,965 @@
+//! Shader translation
+//!
+//! With the `naga` feature, `sg_translate_shader()` translates a single GLSL 450 or WGSL
+//! source with [naga](https://github.com/gfx-rs/naga) to the shader language of a backend,
+//! and reflects the vertex attributes, uniform blocks and images of both shader stages. The
+//! result fills in the complete `SgShaderDesc`:
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! const SHADER: &str = r#"
+//! struct VsParams {
+//!     mvp: mat4x4<f32>,
+//! }
+//!
+//! @group(0) @binding(0) var<uniform> vs_params: VsParams;
+//! @group(1) @binding(0) var tex: texture_2d<f32>;
+//! @group(1) @binding(1) var smp: sampler;
+//!
+//! struct VsOut {
+//!     @builtin(position) pos: vec4<f32>,
+//!     @location(0) uv: vec2<f32>,
+//! }
+//!
+//! @vertex
+//! fn vs_main(@location(0) pos: vec4<f32>, @location(1) uv: vec2<f32>) -> VsOut {
+//!     return VsOut(vs_params.mvp * pos, uv);
+//! }
+//!
+//! @fragment
+//! fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
+//!     return textureSample(tex, smp, in.uv);
+//! }
+//! "#;
+//!
+//! let shader = sg_translate_shader(SgShaderSource::Wgsl(SHADER), sg_query_backend())
+//!     .expect("failed to translate shader");
+//!
+//! let shd = sg_make_shader(&shader.desc());
+//! ```
+//!
+//! The resources of each stage get their bind slots in order of `(group, binding)`: above,
+//! `vs_params` is vertex shader uniform block 0, and `tex` is fragment shader image 0. A
+//! sampler is bound to the slot of the texture it samples. Vertex attribute indices are the
+//! `location` of the vertex shader inputs.
+//!
+//! GLSL sources are compiled once per stage, with either `SG_VERTEX_SHADER` or
+//! `SG_FRAGMENT_SHADER` defined, and `main()` as entry point of both stages.
+//!
+//! Uniform blocks use the `Std140` layout. Their members must be scalars or vectors of
+//! `f32` or `i32`, 4x4 `f32` matrices, or arrays of 4-component vectors and matrices. The
+//! clip space conventions of the source are kept as they are, like `sokol-shdc` does.
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+
+use naga::back::{glsl, hlsl, msl};
+use naga::front::{glsl as glsl_in, wgsl};
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use naga::{AddressSpace, ArraySize, Binding, ConstantInner, Expression, GlobalVariable, Handle,
+           ImageClass, ImageDimension, Module, ResourceBinding, ScalarKind, ScalarValue,
+           ShaderStage, TypeInner, VectorSize};
+
+use super::*;
+use super::ffi::{SG_MAX_SHADERSTAGE_IMAGES, SG_MAX_SHADERSTAGE_UBS, SG_MAX_UB_MEMBERS,
+                 SG_MAX_VERTEX_ATTRIBUTES};
+
+/// The semantic name the HLSL backend of naga gives to vertex shader inputs.
+const LOCATION_SEMANTIC: &str = "LOC";
+
+#[derive(Copy, Clone, Debug)]
+pub enum SgShaderSource<'a> {
+    /// GLSL 450 source of both stages, see the module documentation.
+    Glsl(&'a str),
+    /// WGSL source with one `@vertex` and one `@fragment` entry point.
+    Wgsl(&'a str),
+}
+
+#[derive(Clone, Debug)]
+pub enum SgShaderTranslateError {
+    /// The source couldn't be parsed, with the messages of the parser.
+    Parse(String),
+    /// The source was parsed, but isn't a valid shader.
+    Validation(String),
+    /// The shader couldn't be written in the shader language of the backend.
+    Write(String),
+    /// The shader uses something sokol_gfx can't bind, e.g. a storage buffer, or the
+    /// backend has no shader language naga can write.
+    Unsupported(String),
+}
+
+impl fmt::Display for SgShaderTranslateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SgShaderTranslateError::Parse(message) => write!(f, "failed to parse shader: {}", message),
+            SgShaderTranslateError::Validation(message) => write!(f, "invalid shader: {}", message),
+            SgShaderTranslateError::Write(message) => write!(f, "failed to write shader: {}", message),
+            SgShaderTranslateError::Unsupported(message) => write!(f, "unsupported shader: {}", message),
+        }
+    }
+}
+
+impl error::Error for SgShaderTranslateError {}
+
+/// A shader translated by `sg_translate_shader()`, which owns the sources and reflection
+/// info the `SgShaderDesc` refers to.
+#[derive(Clone, Debug)]
+pub struct SgTranslatedShader {
+    attrs: Vec<TranslatedAttr>,
+    vs: TranslatedStage,
+    fs: TranslatedStage,
+}
+
+#[derive(Clone, Debug)]
+struct TranslatedAttr {
+    name: String,
+    location: usize,
+}
+
+#[derive(Clone, Debug)]
+struct TranslatedStage {
+    source: String,
+    entry: Option<String>,
+    uniform_blocks: Vec<TranslatedUniformBlock>,
+    images: Vec<TranslatedImage>,
+}
+
+#[derive(Clone, Debug)]
+struct TranslatedUniformBlock {
+    size: usize,
+    uniforms: Vec<TranslatedUniform>,
+}
+
+#[derive(Clone, Debug)]
+struct TranslatedUniform {
+    name: String,
+    uniform_type: SgUniformType,
+    array_count: i32,
+}
+
+#[derive(Clone, Debug)]
+struct TranslatedImage {
+    name: String,
+    image_type: SgImageType,
+}
+
+impl SgTranslatedShader {
+    /// Returns the shader description for `sg_make_shader()`.
+    pub fn desc(&self) -> SgShaderDesc<'_> {
+        let num_attrs = self.attrs.iter().map(|attr| attr.location + 1).max().unwrap_or(0);
+        let mut attrs: Vec<SgShaderAttrDesc> = (0..num_attrs).map(|_| Default::default()).collect();
+
+        for attr in &self.attrs {
+            attrs[attr.location] = SgShaderAttrDesc {
+                name: &attr.name,
+                sem_name: LOCATION_SEMANTIC,
+                sem_index: attr.location as i32,
+            };
+        }
+
+        SgShaderDesc {
+            attrs,
+            vs: self.vs.desc(),
+            fs: self.fs.desc(),
+            label: None,
+        }
+    }
+
+    /// Returns the translated vertex shader source.
+    pub fn vs_source(&self) -> &str {
+        &self.vs.source
+    }
+
+    /// Returns the translated fragment shader source.
+    pub fn fs_source(&self) -> &str {
+        &self.fs.source
+    }
+}
+
+impl TranslatedStage {
+    fn desc(&self) -> SgShaderStageDesc<'_> {
+        SgShaderStageDesc {
+            source: Some(&self.source),
+            byte_code: None,
+            entry: self.entry.as_deref(),
+            uniform_blocks: self.uniform_blocks.iter().map(|ub| SgShaderUniformBlockDesc {
+                size: ub.size as i32,
+                layout: SgUniformLayout::Std140,
+                uniforms: ub.uniforms.iter().map(|u| SgShaderUniformDesc {
+                    name: &u.name,
+                    uniform_type: u.uniform_type,
+                    array_count: u.array_count,
+                }).collect(),
+            }).collect(),
+            images: self.images.iter().map(|img| SgShaderImageDesc {
+                name: &img.name,
+                image_type: img.image_type,
+            }).collect(),
+        }
+    }
+}
+
+/// Translates a shader to the shader language of `backend`, usually `sg_query_backend()`.
+///
+/// GL backends get GLSL 330 or GLSL 300 es, D3D11 gets HLSL for shader model 5.0, and the
+/// Metal backends get MSL 1.2. There's no translation for the GLES2 and WGPU backends.
+pub fn sg_translate_shader(source: SgShaderSource, backend: SgBackend) -> Result<SgTranslatedShader, SgShaderTranslateError> {
+    let target = Target::new(backend)?;
+
+    match source {
+        SgShaderSource::Glsl(source) => {
+            let vs = ParsedModule::glsl(source, ShaderStage::Vertex)?;
+            let fs = ParsedModule::glsl(source, ShaderStage::Fragment)?;
+            translate(&vs, &fs, target)
+        }
+        SgShaderSource::Wgsl(source) => {
+            let module = wgsl::parse_str(source)
+                .map_err(|err| SgShaderTranslateError::Parse(err.emit_to_string(source)))?;
+            let parsed = ParsedModule::new(module)?;
+            translate(&parsed, &parsed, target)
+        }
+    }
+}
+
+/// Translates a shader for the active backend and creates it, see `sg_translate_shader()`.
+pub fn sg_make_translated_shader(source: SgShaderSource) -> Result<SgShader, SgShaderTranslateError> {
+    let shader = sg_translate_shader(source, sg_query_backend())?;
+
+    Ok(sg_make_shader(&shader.desc()))
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Target {
+    Glsl(glsl::Version),
+    Hlsl,
+    Msl,
+}
+
+impl Target {
+    fn new(backend: SgBackend) -> Result<Target, SgShaderTranslateError> {
+        match backend {
+            SgBackend::GLCORE33 | SgBackend::Dummy => Ok(Target::Glsl(glsl::Version::Desktop(330))),
+            SgBackend::GLES3 => Ok(Target::Glsl(glsl::Version::Embedded {
+                version: 300,
+                is_webgl: cfg!(target_arch = "wasm32"),
+            })),
+            SgBackend::D3D11 => Ok(Target::Hlsl),
+            SgBackend::MetalIOS | SgBackend::MetalMacOS | SgBackend::MetalSimulator => Ok(Target::Msl),
+            _ => Err(SgShaderTranslateError::Unsupported(
+                format!("no shader translation for the {:?} backend", backend))),
+        }
+    }
+}
+
+struct ParsedModule {
+    module: Module,
+    info: ModuleInfo,
+}
+
+impl ParsedModule {
+    fn new(module: Module) -> Result<ParsedModule, SgShaderTranslateError> {
+        let info = Validator::new(ValidationFlags::all(), Capabilities::empty())
+            .validate(&module)
+            .map_err(|err| SgShaderTranslateError::Validation(error_chain(&err)))?;
+
+        Ok(ParsedModule { module, info })
+    }
+
+    fn glsl(source: &str, stage: ShaderStage) -> Result<ParsedModule, SgShaderTranslateError> {
+        let mut options = glsl_in::Options::from(stage);
+
+        let define = match stage {
+            ShaderStage::Vertex => "SG_VERTEX_SHADER",
+            _ => "SG_FRAGMENT_SHADER",
+        };
+        options.defines.insert(define.to_string(), "1".to_string());
+
+        let module = glsl_in::Frontend::default().parse(&options, source).map_err(|errors| {
+            let messages: Vec<String> = errors.iter()
+                .map(|err| format!("{} at {:?}", err.kind, err.meta))
+                .collect();
+            SgShaderTranslateError::Parse(messages.join("\n"))
+        })?;
+
+        ParsedModule::new(module)
+    }
+
+    fn entry_point(&self, stage: ShaderStage) -> Result<usize, SgShaderTranslateError> {
+        let mut entry_points = self.module.entry_points.iter()
+            .enumerate()
+            .filter(|(_, ep)| ep.stage == stage)
+            .map(|(index, _)| index);
+
+        match (entry_points.next(), entry_points.next()) {
+            (Some(index), None) => Ok(index),
+            (None, _) => Err(SgShaderTranslateError::Unsupported(
+                format!("no {:?} entry point", stage))),
+            (Some(_), Some(_)) => Err(SgShaderTranslateError::Unsupported(
+                format!("more than one {:?} entry point", stage))),
+        }
+    }
+}
+
+fn translate(vs: &ParsedModule, fs: &ParsedModule, target: Target) -> Result<SgTranslatedShader, SgShaderTranslateError> {
+    Ok(SgTranslatedShader {
+        attrs: reflect_attrs(vs, target)?,
+        vs: translate_stage(vs, ShaderStage::Vertex, target)?,
+        fs: translate_stage(fs, ShaderStage::Fragment, target)?,
+    })
+}
+
+fn reflect_attrs(parsed: &ParsedModule, target: Target) -> Result<Vec<TranslatedAttr>, SgShaderTranslateError> {
+    let module = &parsed.module;
+    let ep = &module.entry_points[parsed.entry_point(ShaderStage::Vertex)?];
+
+    let mut locations = Vec::new();
+
+    for arg in &ep.function.arguments {
+        match (&arg.binding, &module.types[arg.ty].inner) {
+            (Some(Binding::Location { location, .. }), _) => locations.push(*location),
+            (None, TypeInner::Struct { members, .. }) => {
+                for member in members {
+                    if let Some(Binding::Location { location, .. }) = member.binding {
+                        locations.push(location);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    locations.into_iter().map(|location| {
+        let location = location as usize;
+
+        if location >= SG_MAX_VERTEX_ATTRIBUTES {
+            return Err(SgShaderTranslateError::Unsupported(
+                format!("vertex input location {} exceeds {} attributes", location, SG_MAX_VERTEX_ATTRIBUTES)));
+        }
+
+        // only the GL backends look up attributes by name
+        let name = match target {
+            Target::Glsl(_) => format!("_p2vs_location{}", location),
+            _ => String::new(),
+        };
+
+        Ok(TranslatedAttr { name, location })
+    }).collect()
+}
+
+/// The resources of one stage, in order of their bind slots.
+struct StageResources {
+    uniform_blocks: Vec<(ResourceBinding, Handle<GlobalVariable>)>,
+    images: Vec<(ResourceBinding, Handle<GlobalVariable>)>,
+    /// The samplers, with the slot of the image they are used with.
+    samplers: Vec<(ResourceBinding, usize)>,
+}
+
+impl StageResources {
+    fn new(parsed: &ParsedModule, ep_index: usize) -> Result<StageResources, SgShaderTranslateError> {
+        let module = &parsed.module;
+        let ep_info = parsed.info.get_entry_point(ep_index);
+
+        let mut uniform_blocks = BTreeMap::new();
+        let mut images = BTreeMap::new();
+        let mut samplers = BTreeMap::new();
+
+        for (handle, var) in module.global_variables.iter() {
+            if ep_info[handle].is_empty() {
+                continue;
+            }
+
+            let binding = match var.binding {
+                Some(ref binding) => binding.clone(),
+                None => continue,
+            };
+
+            match (var.space, &module.types[var.ty].inner) {
+                (AddressSpace::Uniform, _) => {
+                    uniform_blocks.insert(binding, handle);
+                }
+                (AddressSpace::Handle, TypeInner::Image { .. }) => {
+                    images.insert(binding, handle);
+                }
+                (AddressSpace::Handle, TypeInner::Sampler { .. }) => {
+                    samplers.insert(handle, binding);
+                }
+                _ => return Err(SgShaderTranslateError::Unsupported(
+                    format!("{} uses a resource sokol_gfx can't bind", global_name(var)))),
+            }
+        }
+
+        if uniform_blocks.len() > SG_MAX_SHADERSTAGE_UBS {
+            return Err(SgShaderTranslateError::Unsupported(
+                format!("more than {} uniform blocks in one stage", SG_MAX_SHADERSTAGE_UBS)));
+        }
+
+        if images.len() > SG_MAX_SHADERSTAGE_IMAGES {
+            return Err(SgShaderTranslateError::Unsupported(
+                format!("more than {} images in one stage", SG_MAX_SHADERSTAGE_IMAGES)));
+        }
+
+        let images: Vec<_> = images.into_iter().collect();
+        let pairs = sampled_images(module);
+
+        let mut sampler_slots: BTreeMap<Handle<GlobalVariable>, usize> = BTreeMap::new();
+
+        for (slot, (_, image)) in images.iter().enumerate() {
+            let mut image_samplers = pairs.iter()
+                .filter(|(i, s)| i == image && samplers.contains_key(s))
+                .map(|(_, s)| *s);
+
+            if let Some(sampler) = image_samplers.next() {
+                if image_samplers.any(|s| s != sampler) {
+                    return Err(SgShaderTranslateError::Unsupported(
+                        format!("{} is sampled with more than one sampler",
+                                global_name(&module.global_variables[*image]))));
+                }
+
+                if sampler_slots.insert(sampler, slot).is_some_and(|other| other != slot) {
+                    return Err(SgShaderTranslateError::Unsupported(
+                        format!("{} is used with more than one texture",
+                                global_name(&module.global_variables[sampler]))));
+                }
+            }
+        }
+
+        let mut paired_samplers = Vec::with_capacity(samplers.len());
+
+        for (handle, binding) in samplers {
+            let slot = sampler_slots.get(&handle).copied().ok_or_else(|| {
+                SgShaderTranslateError::Unsupported(
+                    format!("{} isn't used with a texture", global_name(&module.global_variables[handle])))
+            })?;
+
+            paired_samplers.push((binding, slot));
+        }
+
+        Ok(StageResources {
+            uniform_blocks: uniform_blocks.into_iter().collect(),
+            images,
+            samplers: paired_samplers,
+        })
+    }
+}
+
+/// Returns the `(texture, sampler)` pairs of all texture samples in the module.
+fn sampled_images(module: &Module) -> Vec<(Handle<GlobalVariable>, Handle<GlobalVariable>)> {
+    let functions = module.functions.iter()
+        .map(|(_, function)| function)
+        .chain(module.entry_points.iter().map(|ep| &ep.function));
+
+    let mut pairs = Vec::new();
+
+    for function in functions {
+        for (_, expr) in function.expressions.iter() {
+            if let Expression::ImageSample { image, sampler, .. } = *expr {
+                if let (Expression::GlobalVariable(image), Expression::GlobalVariable(sampler)) =
+                    (&function.expressions[image], &function.expressions[sampler]) {
+                    pairs.push((*image, *sampler));
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+fn translate_stage(parsed: &ParsedModule, stage: ShaderStage, target: Target) -> Result<TranslatedStage, SgShaderTranslateError> {
+    let module = &parsed.module;
+    let ep_index = parsed.entry_point(stage)?;
+    let ep = &module.entry_points[ep_index];
+    let resources = StageResources::new(parsed, ep_index)?;
+
+    match target {
+        Target::Glsl(version) => {
+            let options = glsl::Options {
+                version,
+                writer_flags: glsl::WriterFlags::empty(),
+                binding_map: Default::default(),
+                zero_initialize_workgroup_memory: false,
+            };
+
+            let pipeline_options = glsl::PipelineOptions {
+                shader_stage: stage,
+                entry_point: ep.name.clone(),
+                multiview: None,
+            };
+
+            let mut source = String::new();
+
+            let reflection = glsl::Writer::new(&mut source, module, &parsed.info, &options,
+                                               &pipeline_options, Default::default())
+                .and_then(|mut writer| writer.write())
+                .map_err(|err| SgShaderTranslateError::Write(err.to_string()))?;
+
+            let mut uniform_blocks = Vec::with_capacity(resources.uniform_blocks.len());
+
+            for (_, handle) in &resources.uniform_blocks {
+                let block_name = &reflection.uniforms[handle];
+                let (name, members) = rewrite_uniform_block(&mut source, block_name)?;
+
+                let names: Vec<String> = match members {
+                    Some(members) => members.iter().map(|member| format!("{}.{}", name, member)).collect(),
+                    None => vec![name],
+                };
+
+                uniform_blocks.push(reflect_uniform_block(module, *handle, names)?);
+            }
+
+            let images = resources.images.iter().map(|(_, handle)| {
+                let name = reflection.texture_mapping.iter()
+                    .find(|(_, mapping)| mapping.texture == *handle)
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default();
+
+                reflect_image(module, *handle, name)
+            }).collect::<Result<_, _>>()?;
+
+            Ok(TranslatedStage {
+                source,
+                entry: None,
+                uniform_blocks,
+                images,
+            })
+        }
+        Target::Hlsl => {
+            let mut binding_map = hlsl::BindingMap::default();
+
+            let bind_target = |register: usize| hlsl::BindTarget {
+                space: 0,
+                register: register as u32,
+                binding_array_size: None,
+            };
+
+            for (slot, (binding, _)) in resources.uniform_blocks.iter().enumerate() {
+                binding_map.insert(binding.clone(), bind_target(slot));
+            }
+            for (slot, (binding, _)) in resources.images.iter().enumerate() {
+                binding_map.insert(binding.clone(), bind_target(slot));
+            }
+            for (binding, slot) in &resources.samplers {
+                binding_map.insert(binding.clone(), bind_target(*slot));
+            }
+
+            let options = hlsl::Options {
+                shader_model: hlsl::ShaderModel::V5_0,
+                binding_map,
+                // skips the resources of the other stage, instead of binding them anywhere
+                fake_missing_bindings: false,
+                ..Default::default()
+            };
+
+            let mut source = String::new();
+
+            let reflection = hlsl::Writer::new(&mut source, &options)
+                .write(module, &parsed.info)
+                .map_err(|err| SgShaderTranslateError::Write(err.to_string()))?;
+
+            let entry = reflection.entry_point_names[ep_index].clone()
+                .map_err(|err| SgShaderTranslateError::Write(err.to_string()))?;
+
+            translated_stage(module, &resources, source, entry)
+        }
+        Target::Msl => {
+            let mut bindings = msl::BindingMap::default();
+
+            for (slot, (binding, _)) in resources.uniform_blocks.iter().enumerate() {
+                bindings.insert(binding.clone(), msl::BindTarget {
+                    buffer: Some(slot as u8),
+                    ..Default::default()
+                });
+            }
+            for (slot, (binding, _)) in resources.images.iter().enumerate() {
+                bindings.insert(binding.clone(), msl::BindTarget {
+                    texture: Some(slot as u8),
+                    ..Default::default()
+                });
+            }
+            for (binding, slot) in &resources.samplers {
+                bindings.insert(binding.clone(), msl::BindTarget {
+                    sampler: Some(msl::BindSamplerTarget::Resource(*slot as u8)),
+                    ..Default::default()
+                });
+            }
+
+            let mut per_entry_point_map = msl::EntryPointResourceMap::default();
+            per_entry_point_map.insert(ep.name.clone(), msl::EntryPointResources {
+                resources: bindings,
+                ..Default::default()
+            });
+
+            let options = msl::Options {
+                lang_version: (1, 2),
+                per_entry_point_map,
+                fake_missing_bindings: false,
+                ..Default::default()
+            };
+
+            let pipeline_options = msl::PipelineOptions {
+                allow_point_size: false,
+            };
+
+            let (source, info) = msl::write_string(module, &parsed.info, &options, &pipeline_options)
+                .map_err(|err| SgShaderTranslateError::Write(err.to_string()))?;
+
+            let entry = info.entry_point_names[ep_index].clone()
+                .map_err(|err| SgShaderTranslateError::Write(err.to_string()))?;
+
+            translated_stage(module, &resources, source, entry)
+        }
+    }
+}
+
+/// Builds a stage for the backends which bind resources by slot, so the names of uniforms
+/// and images are only informative.
+fn translated_stage(module: &Module, resources: &StageResources, source: String, entry: String) -> Result<TranslatedStage, SgShaderTranslateError> {
+    let uniform_blocks = resources.uniform_blocks.iter().map(|(_, handle)| {
+        let var = &module.global_variables[*handle];
+
+        let names = match module.types[var.ty].inner {
+            TypeInner::Struct { ref members, .. } => members.iter()
+                .map(|member| member.name.clone().unwrap_or_default())
+                .collect(),
+            _ => vec![var.name.clone().unwrap_or_default()],
+        };
+
+        reflect_uniform_block(module, *handle, names)
+    }).collect::<Result<_, _>>()?;
+
+    let images = resources.images.iter().map(|(_, handle)| {
+        let name = module.global_variables[*handle].name.clone().unwrap_or_default();
+        reflect_image(module, *handle, name)
+    }).collect::<Result<_, _>>()?;
+
+    Ok(TranslatedStage {
+        source,
+        entry: Some(entry),
+        uniform_blocks,
+        images,
+    })
+}
+
+/// naga writes uniform blocks as GLSL interface blocks, but sokol_gfx sets uniforms by
+/// name with `glUniform*()`. This turns the interface block into a plain uniform of the
+/// block's struct type, and returns the uniform name and the names of the struct members.
+fn rewrite_uniform_block(source: &mut String, block_name: &str) -> Result<(String, Option<Vec<String>>), SgShaderTranslateError> {
+    let invalid = || SgShaderTranslateError::Write(format!("unexpected GLSL for uniform block {}", block_name));
+
+    let pattern = format!(" {} {{ ", block_name);
+
+    let line_start = source.find(&pattern)
+        .map(|pos| source[..pos].rfind('\n').map_or(0, |nl| nl + 1))
+        .ok_or_else(invalid)?;
+    let line_end = source[line_start..].find('\n').map_or(source.len(), |nl| line_start + nl);
+
+    // `[layout(std140) ]uniform <block_name> { <type> <name>; };`
+    let line = &source[line_start..line_end];
+    let inner = line[line.find(&pattern).ok_or_else(invalid)? + pattern.len()..]
+        .strip_suffix("; };")
+        .ok_or_else(invalid)?
+        .to_string();
+
+    let (ty, name) = inner.rsplit_once(' ').ok_or_else(invalid)?;
+    let name = name.split('[').next().unwrap_or(name).to_string();
+
+    let members = struct_members(source, ty.rsplit(' ').next().unwrap_or(ty));
+
+    source.replace_range(line_start..line_end, &format!("uniform {};", inner));
+
+    Ok((name, members))
+}
+
+/// Returns the member names of a struct definition in GLSL source, if there is one.
+fn struct_members(source: &str, ty: &str) -> Option<Vec<String>> {
+    let header = format!("struct {} {{\n", ty);
+    let start = source.find(&header)? + header.len();
+
+    let members = source[start..].lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('}'))
+        .filter_map(|line| line.strip_suffix(';'))
+        .filter_map(|line| line.rsplit(' ').next())
+        .map(|name| name.split('[').next().unwrap_or(name).to_string())
+        .collect();
+
+    Some(members)
+}
+
+/// Reflects a uniform block, and checks that naga placed its members where the `Std140`
+/// layout of sokol_gfx expects them. `names` are the uniform names, one per member.
+fn reflect_uniform_block(module: &Module, handle: Handle<GlobalVariable>, names: Vec<String>) -> Result<TranslatedUniformBlock, SgShaderTranslateError> {
+    let var = &module.global_variables[handle];
+
+    let members: Vec<_> = match module.types[var.ty].inner {
+        TypeInner::Struct { ref members, .. } => members.iter()
+            .map(|member| (member.ty, member.offset as usize))
+            .collect(),
+        _ => vec![(var.ty, 0)],
+    };
+
+    if members.len() != names.len() {
+        return Err(SgShaderTranslateError::Write(
+            format!("failed to reflect the uniforms of {}", global_name(var))));
+    }
+
+    if members.len() > SG_MAX_UB_MEMBERS {
+        return Err(SgShaderTranslateError::Unsupported(
+            format!("{} has more than {} uniforms", global_name(var), SG_MAX_UB_MEMBERS)));
+    }
+
+    let layout = SgUniformLayout::Std140;
+
+    let mut uniforms = Vec::with_capacity(members.len());
+    let mut end_offset: usize = 0;
+
+    for ((ty, offset), name) in members.into_iter().zip(names) {
+        let (uniform_type, array_count) = uniform_type(module, ty)
+            .filter(|&(uniform_type, array_count)| array_count == 1 || layout.supports_array(uniform_type))
+            .ok_or_else(|| SgShaderTranslateError::Unsupported(
+                format!("uniform {} of {} has a type sokol_gfx doesn't support", name, global_name(var))))?;
+
+        let align = layout.uniform_align(uniform_type, array_count);
+        let expected = end_offset.div_ceil(align) * align;
+
+        if offset != expected {
+            return Err(SgShaderTranslateError::Unsupported(
+                format!("uniform {} of {} is at offset {}, but sokol_gfx expects it at offset {}",
+                        name, global_name(var), offset, expected)));
+        }
+
+        end_offset = offset + layout.uniform_size(uniform_type, array_count);
+
+        uniforms.push(TranslatedUniform {
+            name,
+            uniform_type,
+            array_count,
+        });
+    }
+
+    Ok(TranslatedUniformBlock {
+        size: layout.block_size(end_offset),
+        uniforms,
+    })
+}
+
+fn uniform_type(module: &Module, ty: Handle<naga::Type>) -> Option<(SgUniformType, i32)> {
+    match module.types[ty].inner {
+        TypeInner::Array { base, size: ArraySize::Constant(size), .. } => {
+            let array_count = match module.constants[size].inner {
+                ConstantInner::Scalar { value: ScalarValue::Uint(count), .. } => count as i32,
+                ConstantInner::Scalar { value: ScalarValue::Sint(count), .. } => count as i32,
+                _ => return Option::None,
+            };
+
+            Some((base_uniform_type(&module.types[base].inner)?, array_count))
+        }
+        ref inner => Some((base_uniform_type(inner)?, 1)),
+    }
+}
+
+fn base_uniform_type(inner: &TypeInner) -> Option<SgUniformType> {
+    let uniform_type = match *inner {
+        TypeInner::Scalar { kind: ScalarKind::Float, width: 4 } => SgUniformType::Float,
+        TypeInner::Scalar { kind: ScalarKind::Sint, width: 4 } => SgUniformType::Int,
+        TypeInner::Vector { size, kind, width: 4 } => match (kind, size) {
+            (ScalarKind::Float, VectorSize::Bi) => SgUniformType::Float2,
+            (ScalarKind::Float, VectorSize::Tri) => SgUniformType::Float3,
+            (ScalarKind::Float, VectorSize::Quad) => SgUniformType::Float4,
+            (ScalarKind::Sint, VectorSize::Bi) => SgUniformType::Int2,
+            (ScalarKind::Sint, VectorSize::Tri) => SgUniformType::Int3,
+            (ScalarKind::Sint, VectorSize::Quad) => SgUniformType::Int4,
+            _ => return Option::None,
+        },
+        TypeInner::Matrix { columns: VectorSize::Quad, rows: VectorSize::Quad, width: 4 } => SgUniformType::Mat4,
+        _ => return Option::None,
+    };
+
+    Some(uniform_type)
+}
+
+fn reflect_image(module: &Module, handle: Handle<GlobalVariable>, name: String) -> Result<TranslatedImage, SgShaderTranslateError> {
+    let var = &module.global_variables[handle];
+
+    let image_type = match module.types[var.ty].inner {
+        TypeInner::Image { dim, arrayed, class: ImageClass::Sampled { .. } | ImageClass::Depth { .. } } => {
+            match (dim, arrayed) {
+                (ImageDimension::D2, false) => Some(SgImageType::Texture2D),
+                (ImageDimension::D2, true) => Some(SgImageType::TextureArray),
+                (ImageDimension::D3, false) => Some(SgImageType::Texture3D),
+                (ImageDimension::Cube, false) => Some(SgImageType::TextureCube),
+                _ => Option::None,
+            }
+        }
+        _ => Option::None,
+    };
+
+    let image_type = image_type.ok_or_else(|| SgShaderTranslateError::Unsupported(
+        format!("{} has a texture type sokol_gfx doesn't support", global_name(var))))?;
+
+    Ok(TranslatedImage { name, image_type })
+}
+
+fn global_name(var: &GlobalVariable) -> String {
+    match (&var.name, &var.binding) {
+        (Some(name), _) => format!("`{}`", name),
+        (None, Some(binding)) => format!("group {} binding {}", binding.group, binding.binding),
+        (None, None) => "a global".to_string(),
+    }
+}
+
+/// Formats an error with all its sources, as naga's validation errors are nested.
+fn error_chain(err: &dyn error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WGSL: &str = r#"
+struct VsParams {
+    mvp: mat4x4<f32>,
+    lights: array<vec4<f32>, 2>,
+}
+
+@group(0) @binding(0) var<uniform> vs_params: VsParams;
+@group(1) @binding(0) var tex: texture_2d<f32>;
+@group(1) @binding(1) var smp: sampler;
+
+struct VsOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@location(0) pos: vec4<f32>, @location(1) uv: vec2<f32>) -> VsOut {
+    return VsOut(vs_params.mvp * pos + vs_params.lights[1], uv);
+}
+
+@fragment
+fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
+    return textureSample(tex, smp, in.uv);
+}
+"#;
+
+    const GLSL: &str = r#"#version 450
+#ifdef SG_VERTEX_SHADER
+layout(binding = 0) uniform VsParams { mat4 mvp; };
+layout(location = 0) in vec4 pos;
+layout(location = 1) in vec2 uv;
+layout(location = 0) out vec2 v_uv;
+void main() { gl_Position = mvp * pos; v_uv = uv; }
+#else
+layout(binding = 0) uniform texture2D tex;
+layout(binding = 1) uniform sampler smp;
+layout(location = 0) in vec2 v_uv;
+layout(location = 0) out vec4 frag_color;
+void main() { frag_color = texture(sampler2D(tex, smp), v_uv); }
+#endif
+"#;
+
+    fn translate(source: SgShaderSource, backend: SgBackend) -> SgTranslatedShader {
+        sg_translate_shader(source, backend)
+            .unwrap_or_else(|err| panic!("failed to translate shader for {:?}: {}", backend, err))
+    }
+
+    fn uniform_names<'a>(stage: &SgShaderStageDesc<'a>) -> Vec<&'a str> {
+        stage.uniform_blocks[0].uniforms.iter().map(|u| u.name).collect()
+    }
+
+    #[test]
+    fn reflects_wgsl() {
+        for &backend in &[SgBackend::GLCORE33, SgBackend::D3D11, SgBackend::MetalMacOS] {
+            let shader = translate(SgShaderSource::Wgsl(WGSL), backend);
+            let desc = shader.desc();
+
+            assert_eq!(desc.attrs.len(), 2);
+            assert_eq!(desc.vs.uniform_blocks.len(), 1);
+            assert_eq!(desc.vs.uniform_blocks[0].size, 96);
+            assert_eq!(desc.vs.uniform_blocks[0].layout, SgUniformLayout::Std140);
+
+            let uniforms = &desc.vs.uniform_blocks[0].uniforms;
+            assert_eq!(uniforms.len(), 2);
+            assert_eq!((uniforms[0].uniform_type, uniforms[0].array_count), (SgUniformType::Mat4, 1));
+            assert_eq!((uniforms[1].uniform_type, uniforms[1].array_count), (SgUniformType::Float4, 2));
+
+            assert!(desc.fs.uniform_blocks.is_empty());
+            assert_eq!(desc.fs.images.len(), 1);
+            assert_eq!(desc.fs.images[0].image_type, SgImageType::Texture2D);
+        }
+    }
+
+    #[test]
+    fn glcore33_uses_plain_uniforms() {
+        let shader = translate(SgShaderSource::Wgsl(WGSL), SgBackend::GLCORE33);
+        let desc = shader.desc();
+
+        assert!(shader.vs_source().starts_with("#version 330"));
+        assert!(shader.vs_source().contains("uniform VsParams _group_0_binding_0_vs;"));
+        assert!(!shader.vs_source().contains("_block_"));
+        assert_eq!(uniform_names(&desc.vs), ["_group_0_binding_0_vs.mvp", "_group_0_binding_0_vs.lights"]);
+
+        assert_eq!(desc.attrs[0].name, "_p2vs_location0");
+        assert_eq!(desc.attrs[1].name, "_p2vs_location1");
+        assert_eq!(desc.fs.images[0].name, "_group_1_binding_0_fs");
+    }
+
+    #[test]
+    fn glcore33_from_glsl() {
+        let shader = translate(SgShaderSource::Glsl(GLSL), SgBackend::GLCORE33);
+        let desc = shader.desc();
+
+        assert!(!shader.vs_source().contains("_block_"));
+        assert_eq!(desc.vs.uniform_blocks[0].uniforms.len(), 1);
+        assert_eq!(desc.vs.uniform_blocks[0].uniforms[0].uniform_type, SgUniformType::Mat4);
+        assert!(uniform_names(&desc.vs)[0].ends_with(".mvp"));
+        assert_eq!(desc.fs.images.len(), 1);
+    }
+
+    #[test]
+    fn d3d11_uses_registers() {
+        let shader = translate(SgShaderSource::Wgsl(WGSL), SgBackend::D3D11);
+        let desc = shader.desc();
+
+        assert!(shader.vs_source().contains("register(b0)"));
+        assert!(shader.fs_source().contains("register(t0)"));
+        assert!(shader.fs_source().contains("register(s0)"));
+        assert!(!shader.vs_source().contains("register(t0)"));
+
+        assert_eq!(desc.vs.entry, Some("vs_main"));
+        assert_eq!((desc.attrs[1].sem_name, desc.attrs[1].sem_index), ("LOC", 1));
+    }
+
+    #[test]
+    fn metal_uses_argument_indices() {
+        let shader = translate(SgShaderSource::Wgsl(WGSL), SgBackend::MetalMacOS);
+        let desc = shader.desc();
+
+        assert!(shader.vs_source().contains("[[buffer(0)]]"));
+        assert!(shader.fs_source().contains("[[texture(0)]]"));
+        assert!(shader.fs_source().contains("[[sampler(0)]]"));
+        assert!(!shader.vs_source().contains("[[texture(0)]]"));
+
+        assert_eq!(desc.vs.entry, Some("vs_main"));
+        assert_eq!(desc.fs.entry, Some("fs_main"));
+    }
+
+    #[test]
+    fn unsupported_backend() {
+        match sg_translate_shader(SgShaderSource::Wgsl(WGSL), SgBackend::WGPU) {
+            Err(SgShaderTranslateError::Unsupported(_)) => {}
+            other => panic!("expected an unsupported backend error, got {:?}", other.map(|_| ())),
+        }
+    }
+}
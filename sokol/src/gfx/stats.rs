@@ -0,0 +1,214 @@
+//! sokol_gfx frame statistics
+//!
+//! `SgStatsCollector` implements `SgTraceHooks` to count the commands issued
+//! between two calls to `sg_commit()`.
+//!
+//! ```no_run
+//! use sokol::gfx::*;
+//!
+//! let stats = SgStatsCollector::new(60);
+//! sg_install_trace_hooks(stats.clone());
+//!
+//! // ... render a few frames ...
+//!
+//! println!("{} draws last frame", stats.last_frame().num_draws);
+//! println!("{:.1} draws on average", stats.average().num_draws);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use super::*;
+
+/// Commands issued during a single frame.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgFrameStats {
+    pub num_passes: u32,
+    pub num_draws: u32,
+    /// Calls to `sg_apply_pipeline()` with a different pipeline than the one applied before.
+    pub num_pipeline_switches: u32,
+    /// Calls to `sg_apply_bindings()`, whether or not the bindings changed.
+    pub num_apply_bindings: u32,
+    pub num_uniform_updates: u32,
+    pub uniform_bytes: usize,
+    /// Calls to `sg_update_buffer()` and `sg_append_buffer()`.
+    pub num_buffer_updates: u32,
+    pub buffer_bytes: usize,
+    pub num_image_updates: u32,
+}
+
+/// Per-frame average of `SgFrameStats` over the last frames.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SgFrameStatsAverage {
+    pub num_frames: u32,
+    pub num_passes: f32,
+    pub num_draws: f32,
+    pub num_pipeline_switches: f32,
+    pub num_apply_bindings: f32,
+    pub num_uniform_updates: f32,
+    pub uniform_bytes: f32,
+    pub num_buffer_updates: f32,
+    pub buffer_bytes: f32,
+    pub num_image_updates: f32,
+}
+
+struct SgStatsState {
+    window: usize,
+    current: SgFrameStats,
+    last_pipeline: Option<SgPipeline>,
+    frames: VecDeque<SgFrameStats>,
+}
+
+/// Collects `SgFrameStats` for each frame, and their average over a number of frames.
+///
+/// Clones share the same counters, so one clone can be installed with
+/// `sg_install_trace_hooks()` while another one is used to query the results.
+#[derive(Clone)]
+pub struct SgStatsCollector {
+    state: Rc<RefCell<SgStatsState>>,
+}
+
+impl SgStatsCollector {
+    /// Creates a collector which averages over the last `num_frames` frames.
+    pub fn new(num_frames: usize) -> Self {
+        let window = num_frames.max(1);
+
+        SgStatsCollector {
+            state: Rc::new(RefCell::new(SgStatsState {
+                window,
+                current: SgFrameStats::default(),
+                last_pipeline: None,
+                frames: VecDeque::with_capacity(window),
+            })),
+        }
+    }
+
+    /// Returns the statistics of the last committed frame.
+    pub fn last_frame(&self) -> SgFrameStats {
+        self.state.borrow().frames.back().cloned().unwrap_or_default()
+    }
+
+    /// Returns the statistics of the frame in progress, i.e. since the last `sg_commit()`.
+    pub fn current_frame(&self) -> SgFrameStats {
+        self.state.borrow().current
+    }
+
+    /// Returns the per-frame average over the last committed frames.
+    pub fn average(&self) -> SgFrameStatsAverage {
+        let state = self.state.borrow();
+
+        let num_frames = state.frames.len();
+        if num_frames == 0 {
+            return SgFrameStatsAverage::default();
+        }
+
+        let mut sum = SgFrameStatsAverage::default();
+        for frame in &state.frames {
+            sum.num_passes += frame.num_passes as f32;
+            sum.num_draws += frame.num_draws as f32;
+            sum.num_pipeline_switches += frame.num_pipeline_switches as f32;
+            sum.num_apply_bindings += frame.num_apply_bindings as f32;
+            sum.num_uniform_updates += frame.num_uniform_updates as f32;
+            sum.uniform_bytes += frame.uniform_bytes as f32;
+            sum.num_buffer_updates += frame.num_buffer_updates as f32;
+            sum.buffer_bytes += frame.buffer_bytes as f32;
+            sum.num_image_updates += frame.num_image_updates as f32;
+        }
+
+        let n = num_frames as f32;
+
+        SgFrameStatsAverage {
+            num_frames: num_frames as u32,
+            num_passes: sum.num_passes / n,
+            num_draws: sum.num_draws / n,
+            num_pipeline_switches: sum.num_pipeline_switches / n,
+            num_apply_bindings: sum.num_apply_bindings / n,
+            num_uniform_updates: sum.num_uniform_updates / n,
+            uniform_bytes: sum.uniform_bytes / n,
+            num_buffer_updates: sum.num_buffer_updates / n,
+            buffer_bytes: sum.buffer_bytes / n,
+            num_image_updates: sum.num_image_updates / n,
+        }
+    }
+
+    /// Discards all frames collected so far.
+    pub fn reset(&self) {
+        let mut state = self.state.borrow_mut();
+        state.current = SgFrameStats::default();
+        state.last_pipeline = None;
+        state.frames.clear();
+    }
+
+    fn update<F: FnOnce(&mut SgFrameStats)>(&self, f: F) {
+        f(&mut self.state.borrow_mut().current);
+    }
+}
+
+impl SgTraceHooks for SgStatsCollector {
+    fn reset_state_cache(&mut self) {
+        self.state.borrow_mut().last_pipeline = None;
+    }
+
+    fn update_buffer(&mut self, _buf: SgBuffer, size: usize) {
+        self.update(|s| {
+            s.num_buffer_updates += 1;
+            s.buffer_bytes += size;
+        });
+    }
+
+    fn update_image(&mut self, _img: SgImage) {
+        self.update(|s| s.num_image_updates += 1);
+    }
+
+    fn append_buffer(&mut self, _buf: SgBuffer, size: usize, _result: i32) {
+        self.update(|s| {
+            s.num_buffer_updates += 1;
+            s.buffer_bytes += size;
+        });
+    }
+
+    fn begin_default_pass(&mut self, _width: i32, _height: i32) {
+        self.begin_pass(SgPass::default());
+    }
+
+    fn begin_pass(&mut self, _pass: SgPass) {
+        let mut state = self.state.borrow_mut();
+        state.current.num_passes += 1;
+        state.last_pipeline = None;
+    }
+
+    fn apply_pipeline(&mut self, pip: SgPipeline) {
+        let mut state = self.state.borrow_mut();
+        if state.last_pipeline != Some(pip) {
+            state.current.num_pipeline_switches += 1;
+            state.last_pipeline = Some(pip);
+        }
+    }
+
+    fn apply_bindings(&mut self) {
+        self.update(|s| s.num_apply_bindings += 1);
+    }
+
+    fn apply_uniforms(&mut self, _stage: SgShaderStage, _ub_index: i32, size: usize) {
+        self.update(|s| {
+            s.num_uniform_updates += 1;
+            s.uniform_bytes += size;
+        });
+    }
+
+    fn draw(&mut self, _base_element: i32, _num_elements: i32, _num_instances: i32) {
+        self.update(|s| s.num_draws += 1);
+    }
+
+    fn commit(&mut self) {
+        let mut state = self.state.borrow_mut();
+
+        if state.frames.len() == state.window {
+            state.frames.pop_front();
+        }
+
+        let frame = std::mem::take(&mut state.current);
+        state.frames.push_back(frame);
+    }
+}
@@ -0,0 +1,325 @@
+//! sokol_gfx trace hooks
+//!
+//! Installs a Rust implementation of `SgTraceHooks` with `sg_install_trace_hooks()`.
+//! It then gets called for each sokol_gfx function invoked by the application.
+//!
+//! Hooks installed before, e.g. by `sokol_gfx_imgui`, keep being called.
+
+use std::cell::Cell;
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+
+use crate::log::SLogItem;
+
+use super::*;
+
+mod ffi {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::os::raw::c_int;
+    use std::os::raw::c_void;
+
+    use super::super::ffi::{SgBindings, SgBufferDesc, SgImageData, SgImageDesc, SgPassAction};
+    use super::super::ffi::{SgPassDesc, SgPipelineDesc, SgRange, SgShaderDesc};
+    use super::super::{SgBuffer, SgImage, SgPass, SgPipeline, SgShader, SgShaderStage};
+
+    macro_rules! sg_trace_hooks {
+        ($( fn $name:ident($($arg:ident: $ty:ty),*) => |$hooks:ident| $call:expr; )*) => {
+            #[repr(C)]
+            #[derive(Copy, Clone)]
+            pub struct SgTraceHooks {
+                pub user_data: *mut c_void,
+                $( $name: Option<extern fn($($ty,)* *mut c_void)>, )*
+            }
+
+            impl SgTraceHooks {
+                pub fn make(user_data: *mut c_void) -> Self {
+                    SgTraceHooks {
+                        user_data,
+                        $( $name: Some(callbacks::$name), )*
+                    }
+                }
+            }
+
+            mod callbacks {
+                use super::*;
+
+                $(
+                    #[allow(unused_variables)]
+                    pub extern fn $name($($arg: $ty,)* user_data: *mut c_void) {
+                        let state = unsafe {
+                            &mut *(user_data as *mut super::super::SgTraceState)
+                        };
+
+                        if let Some(prev) = state.prev.$name {
+                            prev($($arg,)* state.prev.user_data);
+                        }
+
+                        // resumed by the gfx function which called the hook
+                        crate::log::slog_catch_panic(|| {
+                            let $hooks = &mut state.hooks;
+                            $call;
+                        });
+                    }
+                )*
+            }
+        };
+    }
+
+    sg_trace_hooks! {
+        fn reset_state_cache() => |h| h.reset_state_cache();
+
+        fn make_buffer(desc: *const SgBufferDesc, result: SgBuffer) => |h| h.make_buffer(result);
+        fn make_image(desc: *const SgImageDesc, result: SgImage) => |h| h.make_image(result);
+        fn make_shader(desc: *const SgShaderDesc, result: SgShader) => |h| h.make_shader(result);
+        fn make_pipeline(desc: *const SgPipelineDesc, result: SgPipeline) => |h| h.make_pipeline(result);
+        fn make_pass(desc: *const SgPassDesc, result: SgPass) => |h| h.make_pass(result);
+
+        fn destroy_buffer(buf: SgBuffer) => |h| h.destroy_buffer(buf);
+        fn destroy_image(img: SgImage) => |h| h.destroy_image(img);
+        fn destroy_shader(shd: SgShader) => |h| h.destroy_shader(shd);
+        fn destroy_pipeline(pip: SgPipeline) => |h| h.destroy_pipeline(pip);
+        fn destroy_pass(pass: SgPass) => |h| h.destroy_pass(pass);
+
+        fn update_buffer(buf: SgBuffer, data: *const SgRange) => |h| {
+            h.update_buffer(buf, unsafe { (*data).size })
+        };
+        fn update_image(img: SgImage, data: *const SgImageData) => |h| h.update_image(img);
+        fn append_buffer(buf: SgBuffer, data: *const SgRange, result: c_int) => |h| {
+            h.append_buffer(buf, unsafe { (*data).size }, result)
+        };
+
+        fn begin_default_pass(pass_action: *const SgPassAction, width: c_int, height: c_int) => |h| {
+            h.begin_default_pass(width, height)
+        };
+        fn begin_pass(pass: SgPass, pass_action: *const SgPassAction) => |h| h.begin_pass(pass);
+        fn apply_viewport(x: c_int, y: c_int, width: c_int, height: c_int, origin_top_left: bool) => |h| {
+            h.apply_viewport(x, y, width, height, origin_top_left)
+        };
+        fn apply_scissor_rect(x: c_int, y: c_int, width: c_int, height: c_int, origin_top_left: bool) => |h| {
+            h.apply_scissor_rect(x, y, width, height, origin_top_left)
+        };
+        fn apply_pipeline(pip: SgPipeline) => |h| h.apply_pipeline(pip);
+        fn apply_bindings(bindings: *const SgBindings) => |h| h.apply_bindings();
+        fn apply_uniforms(stage: SgShaderStage, ub_index: c_int, data: *const SgRange) => |h| {
+            h.apply_uniforms(stage, ub_index, unsafe { (*data).size })
+        };
+        fn draw(base_element: c_int, num_elements: c_int, num_instances: c_int) => |h| {
+            h.draw(base_element, num_elements, num_instances)
+        };
+        fn end_pass() => |h| h.end_pass();
+        fn commit() => |h| h.commit();
+
+        fn alloc_buffer(result: SgBuffer) => |h| h.alloc_buffer(result);
+        fn alloc_image(result: SgImage) => |h| h.alloc_image(result);
+        fn alloc_shader(result: SgShader) => |h| h.alloc_shader(result);
+        fn alloc_pipeline(result: SgPipeline) => |h| h.alloc_pipeline(result);
+        fn alloc_pass(result: SgPass) => |h| h.alloc_pass(result);
+
+        fn dealloc_buffer(buf: SgBuffer) => |h| h.dealloc_buffer(buf);
+        fn dealloc_image(img: SgImage) => |h| h.dealloc_image(img);
+        fn dealloc_shader(shd: SgShader) => |h| h.dealloc_shader(shd);
+        fn dealloc_pipeline(pip: SgPipeline) => |h| h.dealloc_pipeline(pip);
+        fn dealloc_pass(pass: SgPass) => |h| h.dealloc_pass(pass);
+
+        fn init_buffer(buf: SgBuffer, desc: *const SgBufferDesc) => |h| h.init_buffer(buf);
+        fn init_image(img: SgImage, desc: *const SgImageDesc) => |h| h.init_image(img);
+        fn init_shader(shd: SgShader, desc: *const SgShaderDesc) => |h| h.init_shader(shd);
+        fn init_pipeline(pip: SgPipeline, desc: *const SgPipelineDesc) => |h| h.init_pipeline(pip);
+        fn init_pass(pass: SgPass, desc: *const SgPassDesc) => |h| h.init_pass(pass);
+
+        fn uninit_buffer(buf: SgBuffer) => |h| h.uninit_buffer(buf);
+        fn uninit_image(img: SgImage) => |h| h.uninit_image(img);
+        fn uninit_shader(shd: SgShader) => |h| h.uninit_shader(shd);
+        fn uninit_pipeline(pip: SgPipeline) => |h| h.uninit_pipeline(pip);
+        fn uninit_pass(pass: SgPass) => |h| h.uninit_pass(pass);
+
+        fn fail_buffer(buf: SgBuffer) => |h| h.fail_buffer(buf);
+        fn fail_image(img: SgImage) => |h| h.fail_image(img);
+        fn fail_shader(shd: SgShader) => |h| h.fail_shader(shd);
+        fn fail_pipeline(pip: SgPipeline) => |h| h.fail_pipeline(pip);
+        fn fail_pass(pass: SgPass) => |h| h.fail_pass(pass);
+
+        fn push_debug_group(name: *const c_char) => |h| {
+            h.push_debug_group(&unsafe { CStr::from_ptr(name) }.to_string_lossy())
+        };
+        fn pop_debug_group() => |h| h.pop_debug_group();
+    }
+
+    mod layout {
+        use sys::raw;
+
+        use super::*;
+
+        assert_layout!(SgTraceHooks, raw::sg_trace_hooks, {
+            user_data => user_data,
+            reset_state_cache => reset_state_cache,
+            commit => commit,
+            pop_debug_group => pop_debug_group,
+        });
+    }
+
+    extern {
+        pub fn sg_install_trace_hooks(trace_hooks: *const SgTraceHooks) -> SgTraceHooks;
+        pub fn sokol_sys_sg_is_validation_log_item(log_item_id: u32) -> bool;
+    }
+}
+
+/// Receives a call for each sokol_gfx function invoked by the application.
+///
+/// All functions have an empty default implementation. Implementations must not
+/// call back into sokol_gfx. If a hook panics, the panic is resumed once the sokol_gfx
+/// function which called it returns.
+#[allow(unused_variables)]
+pub trait SgTraceHooks {
+    fn reset_state_cache(&mut self) {}
+
+    fn make_buffer(&mut self, result: SgBuffer) {}
+    fn make_image(&mut self, result: SgImage) {}
+    fn make_shader(&mut self, result: SgShader) {}
+    fn make_pipeline(&mut self, result: SgPipeline) {}
+    fn make_pass(&mut self, result: SgPass) {}
+
+    fn destroy_buffer(&mut self, buf: SgBuffer) {}
+    fn destroy_image(&mut self, img: SgImage) {}
+    fn destroy_shader(&mut self, shd: SgShader) {}
+    fn destroy_pipeline(&mut self, pip: SgPipeline) {}
+    fn destroy_pass(&mut self, pass: SgPass) {}
+
+    /// `size` is the number of bytes uploaded.
+    fn update_buffer(&mut self, buf: SgBuffer, size: usize) {}
+    fn update_image(&mut self, img: SgImage) {}
+    /// `size` is the number of bytes appended, `result` the offset returned by `sg_append_buffer()`.
+    fn append_buffer(&mut self, buf: SgBuffer, size: usize, result: i32) {}
+
+    fn begin_default_pass(&mut self, width: i32, height: i32) {}
+    fn begin_pass(&mut self, pass: SgPass) {}
+    fn apply_viewport(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {}
+    fn apply_scissor_rect(&mut self, x: i32, y: i32, width: i32, height: i32, origin_top_left: bool) {}
+    fn apply_pipeline(&mut self, pip: SgPipeline) {}
+    fn apply_bindings(&mut self) {}
+    /// `size` is the number of bytes uploaded.
+    fn apply_uniforms(&mut self, stage: SgShaderStage, ub_index: i32, size: usize) {}
+    fn draw(&mut self, base_element: i32, num_elements: i32, num_instances: i32) {}
+    fn end_pass(&mut self) {}
+    fn commit(&mut self) {}
+
+    fn alloc_buffer(&mut self, result: SgBuffer) {}
+    fn alloc_image(&mut self, result: SgImage) {}
+    fn alloc_shader(&mut self, result: SgShader) {}
+    fn alloc_pipeline(&mut self, result: SgPipeline) {}
+    fn alloc_pass(&mut self, result: SgPass) {}
+
+    fn dealloc_buffer(&mut self, buf: SgBuffer) {}
+    fn dealloc_image(&mut self, img: SgImage) {}
+    fn dealloc_shader(&mut self, shd: SgShader) {}
+    fn dealloc_pipeline(&mut self, pip: SgPipeline) {}
+    fn dealloc_pass(&mut self, pass: SgPass) {}
+
+    fn init_buffer(&mut self, buf: SgBuffer) {}
+    fn init_image(&mut self, img: SgImage) {}
+    fn init_shader(&mut self, shd: SgShader) {}
+    fn init_pipeline(&mut self, pip: SgPipeline) {}
+    fn init_pass(&mut self, pass: SgPass) {}
+
+    fn uninit_buffer(&mut self, buf: SgBuffer) {}
+    fn uninit_image(&mut self, img: SgImage) {}
+    fn uninit_shader(&mut self, shd: SgShader) {}
+    fn uninit_pipeline(&mut self, pip: SgPipeline) {}
+    fn uninit_pass(&mut self, pass: SgPass) {}
+
+    fn fail_buffer(&mut self, buf: SgBuffer) {}
+    fn fail_image(&mut self, img: SgImage) {}
+    fn fail_shader(&mut self, shd: SgShader) {}
+    fn fail_pipeline(&mut self, pip: SgPipeline) {}
+    fn fail_pass(&mut self, pass: SgPass) {}
+
+    fn push_debug_group(&mut self, name: &str) {}
+    fn pop_debug_group(&mut self) {}
+
+    /// Called for each message of the sokol_gfx validation layer, which is enabled in
+    /// debug builds.
+    ///
+    /// Each failed check is reported with `SLogLevel::Error`, followed by a final
+    /// `SG_LOGITEM_VALIDATION_FAILED` with `SLogLevel::Panic`, after which `sokol::log`
+    /// aborts the process. With the `dummy` backend, validation is non-fatal, and
+    /// `SG_LOGITEM_VALIDATION_FAILED` is reported with `SLogLevel::Error` instead.
+    fn validation_failed(&mut self, item: &SLogItem) {}
+}
+
+struct SgTraceState {
+    hooks: Box<dyn SgTraceHooks>,
+    prev: ffi::SgTraceHooks,
+}
+
+thread_local! {
+    // sokol_gfx calls the hooks on the thread which uses it, and hooks aren't `Send`
+    static TRACE_STATE: Cell<*mut SgTraceState> = const { Cell::new(null_mut()) };
+}
+
+/// Installs trace hooks, replacing those installed with a previous call.
+///
+/// Must be called after `sg_setup()`, on the thread which uses sokol_gfx.
+pub fn sg_install_trace_hooks<H: SgTraceHooks + 'static>(hooks: H) {
+    sg_uninstall_trace_hooks();
+
+    let state = Box::into_raw(Box::new(SgTraceState {
+        hooks: Box::new(hooks),
+        prev: ffi::SgTraceHooks::make(null_mut()),
+    }));
+
+    unsafe {
+        (*state).prev = ffi::sg_install_trace_hooks(&ffi::SgTraceHooks::make(state as *mut c_void));
+    }
+
+    TRACE_STATE.with(|cell| cell.set(state));
+}
+
+/// Removes the trace hooks installed with `sg_install_trace_hooks()`, and returns them.
+///
+/// `sg_shutdown()` drops installed hooks, so this returns `None` afterwards.
+pub fn sg_uninstall_trace_hooks() -> Option<Box<dyn SgTraceHooks>> {
+    let state = TRACE_STATE.with(|cell| cell.replace(null_mut()));
+
+    if state.is_null() {
+        return None;
+    }
+
+    let state = unsafe {
+        Box::from_raw(state)
+    };
+
+    unsafe {
+        ffi::sg_install_trace_hooks(&state.prev);
+    }
+
+    Some(state.hooks)
+}
+
+/// Drops the installed trace hooks on `sg_shutdown()`, without restoring the previous
+/// ones, which sokol_gfx forgets anyway.
+pub(super) fn sg_trace_shutdown() {
+    let state = TRACE_STATE.with(|cell| cell.replace(null_mut()));
+
+    if !state.is_null() {
+        unsafe {
+            drop(Box::from_raw(state));
+        }
+    }
+}
+
+/// Forwards a message of the validation layer reported through `sokol::log` to the
+/// installed trace hooks.
+pub(crate) fn sg_trace_validation_failed(item: &SLogItem) {
+    if item.tag != "sg" || !unsafe { ffi::sokol_sys_sg_is_validation_log_item(item.item_id) } {
+        return;
+    }
+
+    let state = TRACE_STATE.with(|cell| cell.get());
+
+    if !state.is_null() {
+        unsafe {
+            (*state).hooks.validation_failed(item);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Typed uniform blocks
+//!
+//! `SgUniformBlock` ties a `#[repr(C)]` struct to the `SgShaderUniformBlockDesc` of a shader,
+//! so that `sg_apply_uniform_block()` can pass it without a hand-written byte count. With the
+//! `derive` feature, it can be derived with `#[derive(UniformBlock)]`, which also checks at
+//! compile time that the struct's field offsets and size match the uniform layout.
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! #[repr(C)]
+//! #[derive(Copy, Clone, UniformBlock)]
+//! #[uniform_block(std140)]
+//! struct VsParams {
+//!     mvp: [[f32; 4]; 4],
+//!     // `[[f32; 4]; 4]` is a Mat4, arrays must be marked explicitly
+//!     #[uniform(array)]
+//!     lights: [[f32; 4]; 3],
+//!     // the name defaults to the field name
+//!     #[uniform(name = "time")]
+//!     t: f32,
+//!     // padding, which still counts towards the offsets of the following uniforms
+//!     #[uniform(skip)]
+//!     _pad: [f32; 3],
+//!     color: [f32; 4],
+//! }
+//!
+//! let shd = sg_make_shader(&SgShaderDesc {
+//!     vs: SgShaderStageDesc {
+//!         uniform_blocks: vec![VsParams::uniform_block_desc()],
+//!         ..Default::default()
+//!     },
+//!     ..Default::default()
+//! });
+//!
+//! sg_apply_uniform_block(SgShaderStage::Vertex, 0, &params);
+//! ```
+
+use std::mem;
+
+use super::*;
+
+#[cfg(feature = "derive")]
+pub use sokol_derive::UniformBlock;
+
+/// Layouts which `#[derive(UniformBlock)]` rejects at compile time.
+///
+/// A field which isn't aligned as the uniform layout requires:
+///
+/// ```compile_fail,E0080
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[uniform_block(std140)]
+/// struct Params {
+///     scale: f32,
+///     // std140 aligns vec4 to 16 bytes
+///     color: [f32; 4],
+/// }
+/// ```
+///
+/// A struct which is smaller than the uniform block:
+///
+/// ```compile_fail,E0080
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[uniform_block(std140)]
+/// struct Params {
+///     // std140 rounds the block size up to 16 bytes
+///     scale: f32,
+/// }
+/// ```
+///
+/// An array of a type std140 doesn't support in arrays:
+///
+/// ```compile_fail,E0080
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[uniform_block(std140)]
+/// struct Params {
+///     #[uniform(array)]
+///     weights: [f32; 4],
+/// }
+/// ```
+///
+/// A skipped field which doesn't directly follow the previous uniform:
+///
+/// ```compile_fail,E0080
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[uniform_block(std140)]
+/// struct Params {
+///     scale: f32,
+///     // `repr(C)` aligns u64 to 8 bytes, which leaves a gap before it
+///     #[uniform(skip)]
+///     _pad: u64,
+/// }
+/// ```
+///
+/// The same struct, padded correctly:
+///
+/// ```
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[uniform_block(std140)]
+/// struct Params {
+///     scale: f32,
+///     #[uniform(skip)]
+///     _pad: [f32; 3],
+/// }
+///
+/// assert_eq!(Params::uniforms().len(), 1);
+/// ```
+#[cfg(all(doctest, feature = "derive"))]
+pub struct SgUniformBlockLayoutErrors;
+
+/// A type which can be used as the field of a uniform block.
+pub trait SgUniformField {
+    const UNIFORM_TYPE: SgUniformType;
+}
+
+macro_rules! impl_uniform_field {
+    ($($ty:ty => $uniform_type:ident),* $(,)*) => {
+        $(
+            impl SgUniformField for $ty {
+                const UNIFORM_TYPE: SgUniformType = SgUniformType::$uniform_type;
+            }
+        )*
+    };
+}
+
+impl_uniform_field! {
+    f32 => Float,
+    [f32; 2] => Float2,
+    [f32; 3] => Float3,
+    [f32; 4] => Float4,
+    i32 => Int,
+    [i32; 2] => Int2,
+    [i32; 3] => Int3,
+    [i32; 4] => Int4,
+    [[f32; 4]; 4] => Mat4,
+    [f32; 16] => Mat4,
+}
+
+/// A uniform block struct, see `#[derive(UniformBlock)]`.
+pub trait SgUniformBlock: Copy {
+    const LAYOUT: SgUniformLayout;
+
+    /// Returns the uniforms of the block, in the order of the struct fields.
+    fn uniforms() -> Vec<SgShaderUniformDesc<'static>>;
+
+    /// Returns the uniform block description for `SgShaderStageDesc::uniform_blocks`.
+    fn uniform_block_desc() -> SgShaderUniformBlockDesc<'static> {
+        SgShaderUniformBlockDesc {
+            size: mem::size_of::<Self>() as i32,
+            layout: Self::LAYOUT,
+            uniforms: Self::uniforms(),
+        }
+    }
+}
+
+/// Updates the uniform block at `ub_index`, see `sg_apply_uniforms()`.
+pub fn sg_apply_uniform_block<T: SgUniformBlock>(stage: SgShaderStage, ub_index: i32, data: &T) {
+    sg_apply_uniforms(stage, ub_index, data, mem::size_of::<T>() as i32);
+}
+
+impl SgUniformType {
+    /// Returns the size of a uniform of this type in bytes.
+    pub const fn size(self) -> usize {
+        match self {
+            SgUniformType::Float | SgUniformType::Int => 4,
+            SgUniformType::Float2 | SgUniformType::Int2 => 8,
+            SgUniformType::Float3 | SgUniformType::Int3 => 12,
+            SgUniformType::Float4 | SgUniformType::Int4 => 16,
+            SgUniformType::Mat4 => 64,
+            _ => 0,
+        }
+    }
+}
+
+/// The rules sokol_gfx uses to place uniforms in a uniform block. These functions are
+/// `const`, so `#[derive(UniformBlock)]` can check a struct's layout at compile time.
+impl SgUniformLayout {
+    /// Returns the alignment of a uniform. `Native` uniforms are tightly packed.
+    pub const fn uniform_align(self, uniform_type: SgUniformType, array_count: i32) -> usize {
+        match self {
+            SgUniformLayout::Std140 => {
+                if array_count > 1 {
+                    16
+                } else {
+                    match uniform_type {
+                        SgUniformType::Float | SgUniformType::Int => 4,
+                        SgUniformType::Float2 | SgUniformType::Int2 => 8,
+                        _ => 16,
+                    }
+                }
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns the size of a uniform, including all array elements.
+    pub const fn uniform_size(self, uniform_type: SgUniformType, array_count: i32) -> usize {
+        let array_count = if array_count > 1 { array_count as usize } else { 1 };
+
+        uniform_type.size() * array_count
+    }
+
+    /// Returns the size of a uniform block, given the end offset of its last uniform.
+    pub const fn block_size(self, end_offset: usize) -> usize {
+        match self {
+            SgUniformLayout::Std140 => end_offset.div_ceil(16) * 16,
+            _ => end_offset,
+        }
+    }
+
+    /// Returns whether a uniform can be used as array in this layout. With `Std140`,
+    /// sokol_gfx only supports arrays of `Float4`, `Int4` and `Mat4`.
+    pub const fn supports_array(self, uniform_type: SgUniformType) -> bool {
+        match self {
+            SgUniformLayout::Std140 => matches!(uniform_type,
+                SgUniformType::Float4 | SgUniformType::Int4 | SgUniformType::Mat4),
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Validation
+//!
+//! The `validate()` functions of the resource descriptions check on the Rust side what
+//! doesn't fit into the C structs of sokol_gfx, e.g. more color attachments than
+//! `SG_MAX_COLOR_ATTACHMENTS`, or would only fail in the validation layer of sokol_gfx,
+//! which logs the reason and is disabled in release builds. `sg_make_*()` and
+//! `sg_apply_bindings()` panic with the error.
+//!
+//! The `sg_try_make_*()` functions validate the description, create the resource, and return
+//! an error if sokol_gfx failed to create it, e.g. because a shader didn't compile:
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! match sg_try_make_shader(&desc) {
+//!     Ok(shd) => state.shader = shd,
+//!     // keep the previous shader on errors
+//!     Err(err) => log::error!("failed to reload shader: {}", err),
+//! }
+//! ```
+
+use std::error;
+
+use super::*;
+use super::ffi::{SG_MAX_COLOR_ATTACHMENTS, SG_MAX_SHADERSTAGE_BUFFERS, SG_MAX_SHADERSTAGE_IMAGES,
+                 SG_MAX_SHADERSTAGE_UBS, SG_MAX_UB_MEMBERS, SG_MAX_VERTEX_ATTRIBUTES};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SgError {
+    /// More elements than sokol_gfx supports, e.g. color attachments.
+    TooMany { what: &'static str, count: usize, max: usize },
+    /// A required value isn't set.
+    Missing { what: &'static str },
+    /// A vertex attribute without vertex format. Vertex attributes must be continuous.
+    VertexFormat { attr: usize },
+    /// A vertex attribute refers to a vertex buffer slot out of range.
+    VertexBufferIndex { attr: usize, buffer_index: i32 },
+    /// The stride of a vertex buffer isn't a multiple of 4.
+    VertexStride { buffer: usize, stride: usize },
+    /// A uniform without uniform type, or an array the layout of its uniform block doesn't support.
+    UniformType { stage: SgShaderStage, ub_index: usize, uniform_index: usize },
+    /// The width or height of an image isn't positive.
+    ImageSize { width: i32, height: i32 },
+    /// The size of a buffer doesn't match the size of its data, in bytes.
+    BufferSize { size: usize, data_size: usize },
+    /// Native resources of another render API than the one of the active backend.
+    NativeBackend { resource: &'static str, backend: SgBackend },
+    ImageContent(SgImageContentError),
+    BufferOverflow(SgBufferOverflow),
+    /// sokol_gfx failed to create a resource, e.g. because a shader didn't compile. The
+    /// reason is logged by sokol_gfx.
+    Failed { resource: &'static str },
+    /// sokol_gfx returned an invalid resource id, because the resource pool is exhausted.
+    Invalid { resource: &'static str },
+}
+
+impl fmt::Display for SgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SgError::TooMany { what, count, max } =>
+                write!(f, "{} {}, but sokol_gfx supports at most {}", count, what, max),
+            SgError::Missing { what } =>
+                write!(f, "missing {}", what),
+            SgError::VertexFormat { attr } =>
+                write!(f, "vertex attribute {} has no vertex format", attr),
+            SgError::VertexBufferIndex { attr, buffer_index } =>
+                write!(f, "vertex attribute {} uses vertex buffer {}, which is out of range", attr, buffer_index),
+            SgError::VertexStride { buffer, stride } =>
+                write!(f, "stride {} of vertex buffer {} isn't a multiple of 4", stride, buffer),
+            SgError::UniformType { stage, ub_index, uniform_index } =>
+                write!(f, "uniform {} of {:?} shader uniform block {} has an invalid type",
+                       uniform_index, stage, ub_index),
+            SgError::ImageSize { width, height } =>
+                write!(f, "invalid image size {}x{}", width, height),
+            SgError::BufferSize { size, data_size } =>
+                write!(f, "buffer size is {} bytes, but data has {} bytes", size, data_size),
+            SgError::NativeBackend { resource, backend } =>
+                write!(f, "native {} can't be used with the {:?} backend", resource, backend),
+            SgError::ImageContent(ref err) => err.fmt(f),
+            SgError::BufferOverflow(ref err) => err.fmt(f),
+            SgError::Failed { resource } =>
+                write!(f, "sokol_gfx failed to create the {}", resource),
+            SgError::Invalid { resource } =>
+                write!(f, "no {} created, the resource pool is exhausted", resource),
+        }
+    }
+}
+
+impl error::Error for SgError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SgError::ImageContent(err) => Some(err),
+            SgError::BufferOverflow(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SgImageContentError> for SgError {
+    fn from(err: SgImageContentError) -> Self {
+        SgError::ImageContent(err)
+    }
+}
+
+impl From<SgBufferOverflow> for SgError {
+    fn from(err: SgBufferOverflow) -> Self {
+        SgError::BufferOverflow(err)
+    }
+}
+
+fn check_count(what: &'static str, count: usize, max: usize) -> Result<(), SgError> {
+    if count > max {
+        return Err(SgError::TooMany { what, count, max });
+    }
+
+    Ok(())
+}
+
+impl SgBufferDesc<'_> {
+    pub fn validate(&self) -> Result<(), SgError> {
+        if self.size == 0 {
+            return Err(SgError::Missing { what: "buffer size" });
+        }
+
+        self.native.map_or(Ok(()), |native| native.check_backend())
+    }
+}
+
+impl SgImageDesc<'_> {
+    pub fn validate(&self) -> Result<(), SgError> {
+        if self.width <= 0 || self.height <= 0 {
+            return Err(SgError::ImageSize { width: self.width, height: self.height });
+        }
+
+        check_count("mipmaps", self.num_mipmaps.max(0) as usize, SG_MAX_MIPMAPS)?;
+
+        self.native.map_or(Ok(()), |native| native.check_backend())
+    }
+}
+
+impl SgShaderDesc<'_> {
+    pub fn validate(&self) -> Result<(), SgError> {
+        check_count("vertex attributes", self.attrs.len(), SG_MAX_VERTEX_ATTRIBUTES)?;
+
+        self.vs.validate(SgShaderStage::Vertex)?;
+        self.fs.validate(SgShaderStage::Fragment)
+    }
+}
+
+impl SgShaderStageDesc<'_> {
+    fn validate(&self, stage: SgShaderStage) -> Result<(), SgError> {
+        if self.source.is_none() && self.byte_code.is_none() {
+            return Err(SgError::Missing {
+                what: match stage {
+                    SgShaderStage::Vertex => "vertex shader source or byte code",
+                    _ => "fragment shader source or byte code",
+                },
+            });
+        }
+
+        check_count("uniform blocks", self.uniform_blocks.len(), SG_MAX_SHADERSTAGE_UBS)?;
+        check_count("images", self.images.len(), SG_MAX_SHADERSTAGE_IMAGES)?;
+
+        for (ub_index, ub) in self.uniform_blocks.iter().enumerate() {
+            check_count("uniforms", ub.uniforms.len(), SG_MAX_UB_MEMBERS)?;
+
+            for (uniform_index, uniform) in ub.uniforms.iter().enumerate() {
+                let invalid_type = match uniform.uniform_type {
+                    SgUniformType::_Invalid | SgUniformType::_Num | SgUniformType::_ForceU32 => true,
+                    uniform_type => uniform.array_count > 1 && !ub.layout.supports_array(uniform_type),
+                };
+
+                if invalid_type {
+                    return Err(SgError::UniformType { stage, ub_index, uniform_index });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SgPipelineDesc<'_> {
+    pub fn validate(&self) -> Result<(), SgError> {
+        if self.shader.id == 0 {
+            return Err(SgError::Missing { what: "shader" });
+        }
+
+        check_count("color attachments", self.colors.len(), SG_MAX_COLOR_ATTACHMENTS)?;
+        check_count("vertex buffers", self.layout.buffers.len(), SG_MAX_SHADERSTAGE_BUFFERS)?;
+        check_count("vertex attributes", self.layout.attrs.len(), SG_MAX_VERTEX_ATTRIBUTES)?;
+
+        if self.layout.attrs.is_empty() {
+            return Err(SgError::Missing { what: "vertex attributes" });
+        }
+
+        for (attr, desc) in self.layout.attrs.iter().enumerate() {
+            if desc.format == SgVertexFormat::_Invalid {
+                return Err(SgError::VertexFormat { attr });
+            }
+
+            if desc.buffer_index < 0 || desc.buffer_index as usize >= SG_MAX_SHADERSTAGE_BUFFERS {
+                return Err(SgError::VertexBufferIndex { attr, buffer_index: desc.buffer_index });
+            }
+        }
+
+        for (buffer, desc) in self.layout.buffers.iter().enumerate() {
+            if desc.stride % 4 != 0 {
+                return Err(SgError::VertexStride { buffer, stride: desc.stride });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SgPassDesc<'_> {
+    pub fn validate(&self) -> Result<(), SgError> {
+        if self.color_attachments.is_empty() {
+            return Err(SgError::Missing { what: "color attachments" });
+        }
+
+        check_count("color attachments", self.color_attachments.len(), SG_MAX_COLOR_ATTACHMENTS)?;
+
+        if self.color_attachments.iter().any(|att| att.image.id == 0) {
+            return Err(SgError::Missing { what: "color attachment image" });
+        }
+
+        Ok(())
+    }
+}
+
+impl SgPassAction {
+    pub fn validate(&self) -> Result<(), SgError> {
+        check_count("color attachments", self.colors.len(), SG_MAX_COLOR_ATTACHMENTS)
+    }
+}
+
+impl SgBindings {
+    pub fn validate(&self) -> Result<(), SgError> {
+        check_count("vertex buffers", self.vertex_buffers.len(), SG_MAX_SHADERSTAGE_BUFFERS)?;
+        check_count("vertex buffer offsets", self.vertex_buffer_offsets.len(), SG_MAX_SHADERSTAGE_BUFFERS)?;
+        check_count("vertex shader images", self.vs_images.len(), SG_MAX_SHADERSTAGE_IMAGES)?;
+        check_count("fragment shader images", self.fs_images.len(), SG_MAX_SHADERSTAGE_IMAGES)
+    }
+}
+
+/// Checks the state of a resource sokol_gfx just created. Failed resources are destroyed,
+/// so their pool slot is released.
+fn created<T: SgResource>(id: T, state: SgResourceState, resource: &'static str) -> Result<T, SgError> {
+    match state {
+        SgResourceState::Valid => Ok(id),
+        SgResourceState::Failed => {
+            id.destroy();
+            Err(SgError::Failed { resource })
+        }
+        _ => Err(SgError::Invalid { resource }),
+    }
+}
+
+/// Validates `desc` and creates a buffer, see `sg_make_buffer()`.
+pub fn sg_try_make_buffer<T>(content: Option<&T>, desc: &SgBufferDesc) -> Result<SgBuffer, SgError> {
+    desc.validate()?;
+
+    let buf = sg_make_buffer(content, desc);
+    created(buf, sg_query_buffer_state(buf), "buffer")
+}
+
+/// Checks `desc` against `data`, and creates a buffer with `data` as content, see
+/// `sg_make_buffer_slice()`.
+pub fn sg_try_make_buffer_slice<T: Pod>(data: &[T], desc: &SgBufferDesc) -> Result<SgBuffer, SgError> {
+    check_buffer_slice(bytemuck::cast_slice(data), desc)?;
+
+    let buf = sg_make_buffer_slice(data, desc);
+    created(buf, sg_query_buffer_state(buf), "buffer")
+}
+
+/// Validates `desc` and `content`, and creates an image, see `sg_make_image()`.
+pub fn sg_try_make_image(content: Option<&SgImageContent>, desc: &SgImageDesc) -> Result<SgImage, SgError> {
+    desc.validate()?;
+
+    if let Some(content) = content {
+        content.validate(desc)?;
+    }
+
+    let img = sg_make_image(content, desc);
+    created(img, sg_query_image_state(img), "image")
+}
+
+/// Validates `desc` and creates a shader, see `sg_make_shader()`. Returns `SgError::Failed`
+/// if the shader doesn't compile.
+pub fn sg_try_make_shader(desc: &SgShaderDesc) -> Result<SgShader, SgError> {
+    desc.validate()?;
+
+    let shd = sg_make_shader(desc);
+    created(shd, sg_query_shader_state(shd), "shader")
+}
+
+/// Validates `desc` and creates a pipeline, see `sg_make_pipeline()`.
+pub fn sg_try_make_pipeline(desc: &SgPipelineDesc) -> Result<SgPipeline, SgError> {
+    desc.validate()?;
+
+    let pip = sg_make_pipeline(desc);
+    created(pip, sg_query_pipeline_state(pip), "pipeline")
+}
+
+/// Validates `desc` and creates a pass, see `sg_make_pass()`.
+pub fn sg_try_make_pass(desc: &SgPassDesc) -> Result<SgPass, SgError> {
+    desc.validate()?;
+
+    let pass = sg_make_pass(desc);
+    created(pass, sg_query_pass_state(pass), "pass")
+}
+
+/// Validates `bindings` and applies them, see `sg_apply_bindings()`.
+pub fn sg_try_apply_bindings(bindings: &SgBindings) -> Result<(), SgError> {
+    bindings.validate()?;
+
+    sg_apply_bindings(bindings);
+    Ok(())
+}
+
+/// Validates `content` against the image, and replaces its content, see `sg_update_image()`.
+pub fn sg_try_update_image(img: SgImage, content: &SgImageContent) -> Result<(), SgError> {
+    content.validate(&sg_query_image_desc(img))?;
+
+    sg_update_image(img, content);
+    Ok(())
+}
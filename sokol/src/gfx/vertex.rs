@@ -0,0 +1,164 @@
+//! Vertex layouts
+//!
+//! `SgVertex` describes the memory layout of a `#[repr(C)]` vertex struct, and turns it
+//! into the `SgLayoutDesc` of a pipeline. With the `derive` feature, it can be derived
+//! with `#[derive(Vertex)]`, which maps the field types to `SgVertexFormat` via the
+//! `SgVertexAttr` trait, and computes offsets and stride.
+//!
+//! ```ignore
+//! use sokol::gfx::*;
+//!
+//! #[repr(C)]
+//! #[derive(Copy, Clone, Vertex)]
+//! struct Vertex {
+//!     pos: [f32; 3],
+//!     color: [u8; 4],
+//!     // formats which can't be told apart by type alone can be set explicitly
+//!     #[vertex(format = UByte4)]
+//!     bone_indices: [u8; 4],
+//! }
+//!
+//! #[repr(C)]
+//! #[derive(Copy, Clone, Vertex)]
+//! #[vertex(per_instance, step_rate = 1)]
+//! struct Instance {
+//!     offset: [f32; 3],
+//! }
+//!
+//! // attributes 0..2 are sourced from buffer 0, attribute 3 from buffer 1
+//! let layout = SgLayoutDesc::default()
+//!     .vertex_buffer::<Vertex>()
+//!     .vertex_buffer::<Instance>();
+//! ```
+//!
+//! Fields marked with `#[vertex(skip)]`, e.g. padding, don't produce a vertex attribute.
+
+use std::mem;
+
+use super::*;
+
+#[cfg(feature = "derive")]
+pub use sokol_derive::Vertex;
+
+/// Layouts which `#[derive(Vertex)]` rejects at compile time.
+///
+/// An explicit vertex format which doesn't match the size of its field:
+///
+/// ```compile_fail,E0080
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, Vertex)]
+/// struct Vertex {
+///     pos: [f32; 3],
+///     // Short4N is 8 bytes
+///     #[vertex(format = Short4N)]
+///     normal: [i16; 2],
+/// }
+/// ```
+///
+/// A field type without a default vertex format:
+///
+/// ```compile_fail,E0277
+/// use sokol::gfx::*;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, Vertex)]
+/// struct Vertex {
+///     pos: [f64; 3],
+/// }
+/// ```
+#[cfg(all(doctest, feature = "derive"))]
+pub struct SgVertexLayoutErrors;
+
+/// A type which can be used as the field of a vertex struct, with its default vertex format.
+///
+/// Byte and short vectors map to the normalized formats. The other integer formats need
+/// to be set explicitly with `#[vertex(format = ...)]`.
+pub trait SgVertexAttr {
+    const FORMAT: SgVertexFormat;
+}
+
+macro_rules! impl_vertex_attr {
+    ($($ty:ty => $format:ident),* $(,)*) => {
+        $(
+            impl SgVertexAttr for $ty {
+                const FORMAT: SgVertexFormat = SgVertexFormat::$format;
+            }
+        )*
+    };
+}
+
+impl_vertex_attr! {
+    f32 => Float,
+    [f32; 1] => Float,
+    [f32; 2] => Float2,
+    [f32; 3] => Float3,
+    [f32; 4] => Float4,
+    [i8; 4] => Byte4N,
+    [u8; 4] => UByte4N,
+    [i16; 2] => Short2N,
+    [u16; 2] => UShort2N,
+    [i16; 4] => Short4N,
+    [u16; 4] => UShort4N,
+}
+
+/// The memory layout of a vertex struct, see `#[derive(Vertex)]`.
+pub trait SgVertex: Sized {
+    const STEP_FUNC: SgVertexStep = SgVertexStep::PerVertex;
+    const STEP_RATE: i32 = 1;
+
+    /// Returns the vertex attributes, sourced from the vertex buffer at `buffer_index`.
+    fn attrs(buffer_index: i32) -> Vec<SgVertexAttrDesc>;
+
+    fn buffer_layout() -> SgBufferLayoutDesc {
+        SgBufferLayoutDesc {
+            stride: mem::size_of::<Self>(),
+            step_func: Self::STEP_FUNC,
+            step_rate: Self::STEP_RATE,
+        }
+    }
+
+    /// Returns the layout of a pipeline using a single vertex buffer of this type.
+    fn layout() -> SgLayoutDesc {
+        SgLayoutDesc::default().vertex_buffer::<Self>()
+    }
+}
+
+impl SgLayoutDesc {
+    /// Adds a vertex buffer with the layout of `V`. Its attributes are appended after
+    /// the ones of previously added buffers.
+    pub fn vertex_buffer<V: SgVertex>(mut self) -> Self {
+        let buffer_index = self.buffers.len() as i32;
+
+        self.buffers.push(V::buffer_layout());
+        self.attrs.extend(V::attrs(buffer_index));
+        self
+    }
+}
+
+impl SgVertexFormat {
+    /// Returns the size of an attribute of this format in bytes.
+    pub const fn size(self) -> usize {
+        match self {
+            SgVertexFormat::Float => 4,
+            SgVertexFormat::Float2 => 8,
+            SgVertexFormat::Float3 => 12,
+            SgVertexFormat::Float4 => 16,
+            SgVertexFormat::Byte4 |
+            SgVertexFormat::Byte4N |
+            SgVertexFormat::UByte4 |
+            SgVertexFormat::UByte4N |
+            SgVertexFormat::Short2 |
+            SgVertexFormat::Short2N |
+            SgVertexFormat::UShort2N |
+            SgVertexFormat::UInt10N2 |
+            SgVertexFormat::Half2 => 4,
+            SgVertexFormat::Short4 |
+            SgVertexFormat::Short4N |
+            SgVertexFormat::UShort4N |
+            SgVertexFormat::Half4 => 8,
+            _ => 0,
+        }
+    }
+}
@@ -72,7 +72,30 @@ macro_rules! cstr {
     )
 }
 
+/// Fails the build if a hand-written `#[repr(C)]` mirror doesn't match the size,
+/// alignment and field offsets of the bindgen-generated C struct in `sokol_sys::raw`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assert_layout {
+    ($rust:ty, $c:ty) => {
+        const _: () = assert!(std::mem::size_of::<$rust>() == std::mem::size_of::<$c>(),
+                              concat!("size mismatch: ", stringify!($rust), " vs. ", stringify!($c)));
+        const _: () = assert!(std::mem::align_of::<$rust>() == std::mem::align_of::<$c>(),
+                              concat!("alignment mismatch: ", stringify!($rust), " vs. ", stringify!($c)));
+    };
+    ($rust:ty, $c:ty, { $($rust_field:ident => $c_field:ident),* $(,)* }) => {
+        $crate::assert_layout!($rust, $c);
+        $(
+            const _: () = assert!(std::mem::offset_of!($rust, $rust_field) == std::mem::offset_of!($c, $c_field),
+                                  concat!("offset mismatch: ", stringify!($rust), "::", stringify!($rust_field),
+                                          " vs. ", stringify!($c), "::", stringify!($c_field)));
+        )*
+    };
+}
+
+pub mod alloc;
 pub mod app;
 pub mod audio;
 pub mod gfx;
+pub mod log;
 pub mod time;
@@ -0,0 +1,180 @@
+//! sokol::log - logging callback for the sokol libraries
+//!
+//! Replaces the [sokol_log.h](https://github.com/floooh/sokol/blob/master/sokol_log.h)
+//! default logger with a Rust function.
+//!
+//! By default, log messages of all sokol libraries are forwarded to the
+//! [log](https://crates.io/crates/log) crate. The target is `sokol::<tag>`, e.g.
+//! `sokol::sg` for messages emitted by `sokol_gfx`. A custom sink can be installed
+//! with `slog_set_sink()`, for example to count validation errors in a test.
+//!
+//! Like the default logger of sokol_log.h, messages with `SLogLevel::Panic` abort the
+//! process after they have been forwarded.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic;
+use std::sync::RwLock;
+
+pub(crate) mod ffi {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::os::raw::c_void;
+
+    unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+        if s.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(s).to_str().unwrap_or("<invalid utf-8>"))
+        }
+    }
+
+    /// Logger function passed to sokol in all `logger` slots.
+    pub(crate) extern fn slog_rust_func(tag: *const c_char,
+                                        log_level: u32,
+                                        log_item_id: u32,
+                                        message_or_null: *const c_char,
+                                        line_nr: u32,
+                                        filename_or_null: *const c_char,
+                                        _user_data: *mut c_void) {
+        let item = unsafe {
+            super::SLogItem {
+                tag: to_str(tag).unwrap_or("sokol"),
+                level: super::SLogLevel::from(log_level),
+                item_id: log_item_id,
+                message: to_str(message_or_null),
+                line: line_nr,
+                filename: to_str(filename_or_null),
+            }
+        };
+
+        super::slog_catch_panic(|| super::slog_dispatch(&item));
+
+        // sokol relies on the logger to abort on panics, and may continue into
+        // undefined behaviour otherwise
+        if log_level == 0 {
+            std::process::abort();
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum SLogLevel {
+    Panic = 0,
+    Error = 1,
+    Warning = 2,
+    Info = 3,
+}
+
+impl From<u32> for SLogLevel {
+    fn from(level: u32) -> Self {
+        match level {
+            0 => SLogLevel::Panic,
+            1 => SLogLevel::Error,
+            2 => SLogLevel::Warning,
+            _ => SLogLevel::Info,
+        }
+    }
+}
+
+/// A single log message emitted by one of the sokol libraries.
+#[derive(Debug)]
+pub struct SLogItem<'a> {
+    /// Short name of the emitting library, e.g. `sapp`, `sg` or `saudio`.
+    pub tag: &'a str,
+    pub level: SLogLevel,
+    /// The library's log item id, e.g. the `SG_LOGITEM_*` enum value.
+    pub item_id: u32,
+    /// The message text. This is `None` in release builds of the C libraries.
+    pub message: Option<&'a str>,
+    pub line: u32,
+    pub filename: Option<&'a str>,
+}
+
+type SLogSink = Box<dyn Fn(&SLogItem) + Send + Sync>;
+
+static SINK: RwLock<Option<SLogSink>> = RwLock::new(None);
+
+thread_local! {
+    static PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+}
+
+/// Installs a function which receives all log messages, instead of the `log` crate.
+///
+/// The sink can be called from any thread, e.g. the `sokol_audio` thread. It must
+/// not call `slog_set_sink()` or `slog_reset_sink()` itself.
+///
+/// If the sink panics, e.g. on a failed assertion in a test, the panic is resumed once
+/// the sokol function which logged the message returns to Rust. Functions which can
+/// log outside of a call from Rust, e.g. on the `sokol_audio` thread, or during the
+/// `sapp_run()` loop, keep it until `slog_resume_panic()` is called on the same thread.
+pub fn slog_set_sink<F>(sink: F)
+    where F: Fn(&SLogItem) + Send + Sync + 'static {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(sink));
+}
+
+/// Removes the sink installed with `slog_set_sink()`, forwarding to the `log` crate again.
+pub fn slog_reset_sink() {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Resumes a panic of the sink installed with `slog_set_sink()`, or of another Rust
+/// callback called from C, if one panicked on this thread since the last call.
+///
+/// This is called by all `gfx` functions which can log errors or call trace hooks and
+/// commit listeners, e.g. `sg_make_buffer()`. While the thread is already unwinding,
+/// e.g. when an `SgOwned` handle is dropped, the panic is kept.
+pub fn slog_resume_panic() {
+    if std::thread::panicking() {
+        return;
+    }
+
+    if let Some(payload) = PANIC.with(|panic| panic.borrow_mut().take()) {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Runs a Rust callback called from C. Unwinding into C is undefined behaviour, so a
+/// panic is kept until control is back in Rust, see `slog_resume_panic()`.
+pub(crate) fn slog_catch_panic<F: FnOnce()>(f: F) {
+    if let Err(payload) = panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        PANIC.with(|panic| {
+            panic.borrow_mut().get_or_insert(payload);
+        });
+    }
+}
+
+fn slog_dispatch(item: &SLogItem) {
+    crate::gfx::sg_trace_validation_failed(item);
+
+    let sink = SINK.read().unwrap_or_else(|e| e.into_inner());
+
+    match &*sink {
+        Some(sink) => sink(item),
+        None => slog_to_log(item),
+    }
+}
+
+fn slog_to_log(item: &SLogItem) {
+    let level = match item.level {
+        SLogLevel::Panic | SLogLevel::Error => ::log::Level::Error,
+        SLogLevel::Warning => ::log::Level::Warn,
+        SLogLevel::Info => ::log::Level::Info,
+    };
+
+    let target = format!("sokol::{}", item.tag);
+
+    if !::log::log_enabled!(target: &target, level) {
+        return;
+    }
+
+    let message = item.message.unwrap_or("");
+
+    ::log::logger().log(&::log::Record::builder()
+        .args(format_args!("{} (item id {})", message.trim_end(), item.item_id))
+        .level(level)
+        .target(&target)
+        .file(item.filename)
+        .line(Some(item.line))
+        .build());
+}
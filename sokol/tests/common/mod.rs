@@ -0,0 +1,65 @@
+//! Resources shared by the headless tests.
+
+#![allow(dead_code)]
+
+use sokol::gfx::*;
+
+/// A GLSL 330 shader with a single `position` vertex attribute.
+pub fn make_shader() -> SgShader {
+    sg_make_shader(&SgShaderDesc {
+        attrs: vec![
+            SgShaderAttrDesc {
+                name: "position",
+                sem_name: "POSITION",
+                sem_index: 0,
+            },
+        ],
+        vs: SgShaderStageDesc {
+            source: Some("#version 330\nin vec4 position;\nvoid main() { gl_Position = position; }\n"),
+            ..Default::default()
+        },
+        fs: SgShaderStageDesc {
+            source: Some("#version 330\nout vec4 frag_color;\nvoid main() { frag_color = vec4(1.0); }\n"),
+            ..Default::default()
+        },
+        label: Some("headless-shader"),
+    })
+}
+
+/// A pipeline for `make_shader()`, with a single `Float4` vertex attribute.
+pub fn make_pipeline(shd: SgShader) -> SgPipeline {
+    sg_make_pipeline(&SgPipelineDesc {
+        shader: shd,
+        layout: SgLayoutDesc {
+            attrs: vec![
+                SgVertexAttrDesc {
+                    format: SgVertexFormat::Float4,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// A vertex buffer with one triangle for `make_pipeline()`.
+pub fn make_vertex_buffer() -> SgBuffer {
+    sg_make_buffer(Some(&[0.0f32; 12]), &SgBufferDesc {
+        size: 48,
+        ..Default::default()
+    })
+}
+
+/// Draws one triangle in a default pass, and commits the frame.
+pub fn draw_frame(pip: SgPipeline, buf: SgBuffer) {
+    sg_begin_default_pass(&SgPassAction::default(), 64, 64);
+    sg_apply_pipeline(pip);
+    sg_apply_bindings(&SgBindings {
+        vertex_buffers: vec![buf],
+        ..Default::default()
+    });
+    sg_draw(0, 3, 1);
+    sg_end_pass();
+    sg_commit();
+}
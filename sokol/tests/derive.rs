@@ -0,0 +1,167 @@
+//! Checks the descriptions generated by `#[derive(Vertex)]` and `#[derive(UniformBlock)]`,
+//! and uses them with the dummy backend.
+//!
+//! ```sh
+//! cargo test -p sokol --test derive --features derive,dummy
+//! ```
+
+use std::mem;
+
+use sokol::gfx::*;
+
+#[repr(C)]
+#[derive(Copy, Clone, Vertex)]
+struct Vertex {
+    pos: [f32; 4],
+    #[vertex(skip)]
+    _pad: [f32; 2],
+    #[vertex(format = UByte4)]
+    bone_indices: [u8; 4],
+    color: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Vertex)]
+#[vertex(per_instance, step_rate = 2)]
+struct Instance {
+    offset: [f32; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, UniformBlock)]
+#[uniform_block(std140)]
+struct VsParams {
+    mvp: [[f32; 4]; 4],
+    #[uniform(array)]
+    lights: [[f32; 4]; 3],
+    #[uniform(name = "tint_color")]
+    tint: [f32; 3],
+    // a vec3 followed by a vec4 needs explicit padding
+    #[uniform(skip)]
+    _pad: f32,
+    color: [f32; 4],
+    scale: [f32; 2],
+    #[uniform(skip)]
+    _pad2: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, UniformBlock)]
+struct FsParams {
+    tint: [f32; 3],
+    #[uniform(array)]
+    weights: [f32; 2],
+}
+
+fn shader_desc(vs_uniform_blocks: Vec<SgShaderUniformBlockDesc>) -> SgShaderDesc {
+    SgShaderDesc {
+        attrs: vec![
+            SgShaderAttrDesc {
+                name: "position",
+                sem_name: "POSITION",
+                sem_index: 0,
+            },
+        ],
+        vs: SgShaderStageDesc {
+            source: Some("#version 330\nin vec4 position;\nvoid main() { gl_Position = position; }\n"),
+            uniform_blocks: vs_uniform_blocks,
+            ..Default::default()
+        },
+        fs: SgShaderStageDesc {
+            source: Some("#version 330\nout vec4 frag_color;\nvoid main() { frag_color = vec4(1.0); }\n"),
+            ..Default::default()
+        },
+        label: None,
+    }
+}
+
+#[test]
+fn vertex_layout() {
+    let layout = SgLayoutDesc::default()
+        .vertex_buffer::<Vertex>()
+        .vertex_buffer::<Instance>();
+
+    let buffers: Vec<_> = layout.buffers.iter()
+        .map(|b| (b.stride, b.step_func, b.step_rate))
+        .collect();
+    assert_eq!(buffers, [
+        (mem::size_of::<Vertex>(), SgVertexStep::PerVertex, 1),
+        (mem::size_of::<Instance>(), SgVertexStep::PerInstance, 2),
+    ]);
+
+    let attrs: Vec<_> = layout.attrs.iter()
+        .map(|a| (a.buffer_index, a.offset, a.format))
+        .collect();
+    assert_eq!(attrs, [
+        (0, 0, SgVertexFormat::Float4),
+        (0, 24, SgVertexFormat::UByte4),
+        (0, 28, SgVertexFormat::UByte4N),
+        (1, 0, SgVertexFormat::Float3),
+    ]);
+}
+
+#[test]
+fn uniform_block_desc() {
+    let desc = VsParams::uniform_block_desc();
+    assert_eq!(desc.size, 160);
+    assert_eq!(desc.layout, SgUniformLayout::Std140);
+
+    let uniforms: Vec<_> = desc.uniforms.iter()
+        .map(|u| (u.name, u.uniform_type, u.array_count))
+        .collect();
+    assert_eq!(uniforms, [
+        ("mvp", SgUniformType::Mat4, 1),
+        ("lights", SgUniformType::Float4, 3),
+        ("tint_color", SgUniformType::Float3, 1),
+        ("color", SgUniformType::Float4, 1),
+        ("scale", SgUniformType::Float2, 1),
+    ]);
+
+    let desc = FsParams::uniform_block_desc();
+    assert_eq!(desc.size, 20);
+    assert_eq!(desc.layout, SgUniformLayout::Native);
+
+    let uniforms: Vec<_> = desc.uniforms.iter()
+        .map(|u| (u.name, u.uniform_type, u.array_count))
+        .collect();
+    assert_eq!(uniforms, [
+        ("tint", SgUniformType::Float3, 1),
+        ("weights", SgUniformType::Float, 2),
+    ]);
+}
+
+#[test]
+fn draw() {
+    sg_run_headless(&SgDesc::default(), || {
+        let shd = sg_try_make_shader(&shader_desc(vec![VsParams::uniform_block_desc()])).unwrap();
+
+        let pip = sg_try_make_pipeline(&SgPipelineDesc {
+            shader: shd,
+            layout: Vertex::layout(),
+            ..Default::default()
+        }).unwrap();
+
+        let buf = sg_make_buffer_slice(&[0u8; 3 * mem::size_of::<Vertex>()], &SgBufferDesc::default());
+
+        let params = VsParams {
+            mvp: [[0.0; 4]; 4],
+            lights: [[0.0; 4]; 3],
+            tint: [1.0; 3],
+            _pad: 0.0,
+            color: [1.0; 4],
+            scale: [1.0; 2],
+            _pad2: [0.0; 2],
+        };
+
+        sg_begin_default_pass(&SgPassAction::default(), 64, 64);
+        sg_apply_pipeline(pip);
+        sg_try_apply_bindings(&SgBindings {
+            vertex_buffers: vec![buf],
+            ..Default::default()
+        }).unwrap();
+        sg_apply_uniform_block(SgShaderStage::Vertex, 0, &params);
+        sg_draw(0, 3, 1);
+        sg_end_pass();
+        sg_commit();
+    });
+}
@@ -0,0 +1,185 @@
+//! Creates resources with the dummy backend, without a window or render context.
+//!
+//! ```sh
+//! cargo test -p sokol --test headless --features dummy
+//! ```
+
+use std::cell::Cell;
+use std::panic;
+use std::rc::Rc;
+
+use sokol::gfx::*;
+
+use common::{make_pipeline, make_shader, make_vertex_buffer};
+
+mod common;
+
+#[test]
+fn make_buffer() {
+    sg_run_headless(&SgDesc::default(), || {
+        let buf = sg_make_buffer(Some(&[0.0f32; 12]), &SgBufferDesc {
+            size: 48,
+            ..Default::default()
+        });
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+
+        sg_destroy_buffer(buf);
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Invalid);
+    });
+}
+
+#[test]
+fn make_shader_and_pipeline() {
+    sg_run_headless(&SgDesc::default(), || {
+        let shd = make_shader();
+        assert_eq!(sg_query_shader_state(shd), SgResourceState::Valid);
+
+        let pip = sg_make_pipeline(&SgPipelineDesc {
+            shader: shd,
+            layout: SgLayoutDesc {
+                attrs: vec![
+                    SgVertexAttrDesc {
+                        format: SgVertexFormat::Float4,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(sg_query_pipeline_state(pip), SgResourceState::Valid);
+
+        sg_destroy_pipeline(pip);
+        sg_destroy_shader(shd);
+    });
+}
+
+#[test]
+fn commit_listener() {
+    sg_run_headless(&SgDesc::default(), || {
+        let count = Rc::new(Cell::new(0));
+
+        let counter = count.clone();
+        let listener = sg_add_commit_listener(move || counter.set(counter.get() + 1)).unwrap();
+
+        for _ in 0..3 {
+            sg_commit();
+        }
+        assert_eq!(count.get(), 3);
+
+        assert!(sg_remove_commit_listener(listener));
+        sg_commit();
+        assert_eq!(count.get(), 3);
+
+        // dropping the handle removes the listener too
+        let counter = count.clone();
+        let listener = sg_add_commit_listener(move || counter.set(counter.get() + 10)).unwrap();
+        sg_commit();
+        drop(listener);
+        sg_commit();
+        assert_eq!(count.get(), 13);
+
+        // both closures have been dropped
+        assert_eq!(Rc::strong_count(&count), 1);
+    });
+}
+
+#[test]
+fn commit_listener_panic_is_resumed() {
+    sg_run_headless(&SgDesc::default(), || {
+        let _listener = sg_add_commit_listener(|| panic!("listener panic")).unwrap();
+
+        assert!(panic::catch_unwind(sg_commit).is_err());
+    });
+}
+
+struct CountEndPass(Rc<Cell<u32>>);
+
+impl SgTraceHooks for CountEndPass {
+    fn end_pass(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn pass_encoder() {
+    sg_run_headless(&SgDesc::default(), || {
+        let end_passes = Rc::new(Cell::new(0));
+        sg_install_trace_hooks(CountEndPass(end_passes.clone()));
+
+        let pip = make_pipeline(make_shader());
+        let buf = make_vertex_buffer();
+        let pass_action = SgPassAction::default();
+
+        {
+            let mut pass = SgPassEncoder::begin_default(&pass_action, 64, 64);
+
+            let nested = panic::catch_unwind(|| SgPassEncoder::begin_default(&pass_action, 64, 64));
+            assert!(nested.is_err());
+
+            let mut pip = pass.apply_pipeline(pip);
+            pip.apply_bindings(&SgBindings {
+                vertex_buffers: vec![buf],
+                ..Default::default()
+            });
+            pip.draw(0, 3, 1);
+
+            assert_eq!(end_passes.get(), 0);
+        }
+
+        // the pass ended when the encoder was dropped
+        assert_eq!(end_passes.get(), 1);
+
+        SgPassEncoder::begin_default(&pass_action, 64, 64).end();
+        assert_eq!(end_passes.get(), 2);
+        sg_commit();
+
+        // a forgotten encoder only blocks new passes until the frame is committed
+        std::mem::forget(SgPassEncoder::begin_default(&pass_action, 64, 64));
+        sg_end_pass();
+        sg_commit();
+
+        SgPassEncoder::begin_default(&pass_action, 64, 64).end();
+        sg_commit();
+        assert_eq!(end_passes.get(), 4);
+
+        sg_uninstall_trace_hooks();
+    });
+}
+
+#[test]
+fn contexts() {
+    sg_run_headless(&SgDesc::default(), || {
+        let ctx_id = |buf| sg_query_buffer_info(buf).slot.ctx_id;
+
+        let buf = make_vertex_buffer();
+
+        // resources belong to the context which was active when they were created
+        let ctx = sg_setup_context();
+        let ctx_buf = make_vertex_buffer();
+        assert_ne!(ctx_id(ctx_buf), ctx_id(buf));
+
+        let other_ctx = sg_setup_context();
+        let other_buf = make_vertex_buffer();
+        assert_ne!(ctx_id(other_buf), ctx_id(ctx_buf));
+
+        sg_activate_context(ctx);
+        assert_eq!(ctx_id(make_vertex_buffer()), ctx_id(ctx_buf));
+
+        // discarding a context destroys its resources, and only those
+        sg_discard_context(other_ctx);
+        assert_eq!(sg_query_buffer_state(other_buf), SgResourceState::Invalid);
+        assert_eq!(sg_query_buffer_state(ctx_buf), SgResourceState::Valid);
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+
+        // dropping an owned context discards it
+        let owned_ctx = SgOwnedContext::new(sg_setup_context());
+        let owned_buf = make_vertex_buffer();
+        assert_ne!(ctx_id(owned_buf), ctx_id(ctx_buf));
+
+        drop(owned_ctx);
+        assert_eq!(sg_query_buffer_state(owned_buf), SgResourceState::Invalid);
+        assert_eq!(sg_query_buffer_state(ctx_buf), SgResourceState::Valid);
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+    });
+}
@@ -0,0 +1,91 @@
+//! Checks that the sink installed with `slog_set_sink()` receives the messages of
+//! sokol_gfx, and that its panics fail the caller instead of aborting the process.
+//!
+//! ```sh
+//! cargo test -p sokol --test log --features dummy
+//! ```
+
+use std::panic;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use sokol::gfx::*;
+use sokol::log::*;
+
+fn make_buffer() -> SgBuffer {
+    sg_make_buffer(Some(&[0.0f32; 8]), &SgBufferDesc {
+        size: 32,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn no_validation_errors() {
+    let errors = Arc::new(Mutex::new(Vec::new()));
+
+    sg_run_headless(&SgDesc::default(), || {
+        let sink_errors = errors.clone();
+        slog_set_sink(move |item| {
+            if item.level <= SLogLevel::Error {
+                sink_errors.lock().unwrap().push(item.item_id);
+            }
+        });
+
+        let buf = make_buffer();
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+        sg_destroy_buffer(buf);
+
+        slog_reset_sink();
+    });
+
+    assert_eq!(*errors.lock().unwrap(), Vec::<u32>::new());
+}
+
+#[test]
+fn sink_panic_is_resumed() {
+    let desc = SgDesc {
+        buffer_pool_size: 1,
+        ..Default::default()
+    };
+
+    let result = sg_run_headless(&desc, || {
+        slog_set_sink(|item| {
+            assert!(item.level > SLogLevel::Error, "sokol error {}", item.item_id);
+        });
+
+        // the second buffer exhausts the pool, which is logged as an error
+        let result = panic::catch_unwind(|| {
+            make_buffer();
+            make_buffer();
+        });
+
+        slog_reset_sink();
+        result
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn sink_panic_is_resumed_by_the_logging_call() {
+    let desc = SgDesc {
+        buffer_pool_size: 1,
+        ..Default::default()
+    };
+
+    sg_run_headless(&desc, || {
+        slog_set_sink(|item| {
+            assert!(item.level > SLogLevel::Error, "sokol error {}", item.item_id);
+        });
+
+        let buf = sg_alloc_buffer();
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Alloc);
+
+        // the failed allocation raises the panic itself, not a later call
+        let result = panic::catch_unwind(sg_alloc_buffer);
+        slog_reset_sink();
+
+        assert!(result.is_err());
+        sg_dealloc_buffer(buf);
+    });
+}
@@ -0,0 +1,113 @@
+//! Creates and destroys thousands of shaders with the dummy backend, and checks
+//! that marshalling their descriptions to C doesn't leak any memory.
+//!
+//! ```sh
+//! cargo test -p sokol --test shader_churn --features dummy
+//! ```
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use sokol::alloc::*;
+use sokol::gfx::*;
+
+const NUM_WARMUP_SHADERS: usize = 100;
+const NUM_SHADERS: usize = 10_000;
+
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn make_shader(index: usize) -> SgShader {
+    let source = format!("#version 330\n// shader #{}\nvoid main() {{}}\n", index);
+    let label = format!("shader #{}", index);
+
+    sg_make_shader(&SgShaderDesc {
+        attrs: vec![
+            SgShaderAttrDesc {
+                name: "position",
+                sem_name: "POSITION",
+                sem_index: 0,
+            },
+        ],
+        vs: SgShaderStageDesc {
+            source: Some(&source),
+            entry: Some("main"),
+            uniform_blocks: vec![
+                SgShaderUniformBlockDesc {
+                    size: 64,
+                    uniforms: vec![
+                        SgShaderUniformDesc {
+                            name: "mvp",
+                            uniform_type: SgUniformType::Mat4,
+                            array_count: 1,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+        fs: SgShaderStageDesc {
+            source: Some(&source),
+            images: vec![
+                SgShaderImageDesc {
+                    name: "tex",
+                    image_type: SgImageType::Texture2D,
+                },
+            ],
+            ..Default::default()
+        },
+        label: Some(&label),
+    })
+}
+
+fn churn(num_shaders: usize) {
+    for index in 0..num_shaders {
+        let shd = make_shader(index);
+        assert_eq!(sg_query_shader_state(shd), SgResourceState::Valid);
+        sg_destroy_shader(shd);
+    }
+}
+
+#[test]
+fn shader_churn() {
+    // route sokol_gfx allocations through the counting allocator, too
+    salloc_setup(SAllocDesc {
+        allocator: SAllocator::Global,
+        tracking: true,
+    });
+
+    sg_run_headless(&SgDesc::default(), || {
+        churn(NUM_WARMUP_SHADERS);
+
+        let rust_before = LIVE_BYTES.load(Ordering::Relaxed);
+        let sokol_before = salloc_query_stats(SAllocModule::Gfx).live_bytes;
+
+        churn(NUM_SHADERS);
+
+        let rust_after = LIVE_BYTES.load(Ordering::Relaxed);
+        let sokol_after = salloc_query_stats(SAllocModule::Gfx).live_bytes;
+
+        assert_eq!(rust_before, rust_after, "memory leaked while creating shaders");
+        assert_eq!(sokol_before, sokol_after, "sokol_gfx memory leaked while creating shaders");
+    });
+}
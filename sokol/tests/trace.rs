@@ -0,0 +1,175 @@
+//! Runs trace hooks with the dummy backend.
+//!
+//! ```sh
+//! cargo test -p sokol --test trace --features dummy
+//! ```
+
+use std::cell::RefCell;
+use std::panic;
+use std::rc::Rc;
+
+use sokol::gfx::*;
+
+use common::{draw_frame, make_pipeline, make_shader, make_vertex_buffer};
+
+mod common;
+
+#[derive(Default)]
+struct Recorder {
+    calls: Rc<RefCell<Vec<String>>>,
+}
+
+impl Recorder {
+    fn record(&self, call: String) {
+        self.calls.borrow_mut().push(call);
+    }
+}
+
+impl SgTraceHooks for Recorder {
+    fn make_buffer(&mut self, _result: SgBuffer) {
+        self.record("make_buffer".to_string());
+    }
+
+    fn make_shader(&mut self, _result: SgShader) {
+        self.record("make_shader".to_string());
+    }
+
+    fn make_pipeline(&mut self, _result: SgPipeline) {
+        self.record("make_pipeline".to_string());
+    }
+
+    fn begin_default_pass(&mut self, width: i32, height: i32) {
+        self.record(format!("begin_default_pass({}, {})", width, height));
+    }
+
+    fn apply_pipeline(&mut self, _pip: SgPipeline) {
+        self.record("apply_pipeline".to_string());
+    }
+
+    fn apply_bindings(&mut self) {
+        self.record("apply_bindings".to_string());
+    }
+
+    fn draw(&mut self, base_element: i32, num_elements: i32, num_instances: i32) {
+        self.record(format!("draw({}, {}, {})", base_element, num_elements, num_instances));
+    }
+
+    fn end_pass(&mut self) {
+        self.record("end_pass".to_string());
+    }
+
+    fn commit(&mut self) {
+        self.record("commit".to_string());
+    }
+}
+
+#[test]
+fn install_and_uninstall() {
+    sg_run_headless(&SgDesc::default(), || {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+
+        sg_install_trace_hooks(Recorder {
+            calls: calls.clone(),
+        });
+
+        let shd = make_shader();
+        let pip = make_pipeline(shd);
+        let buf = make_vertex_buffer();
+        draw_frame(pip, buf);
+
+        assert_eq!(*calls.borrow(), [
+            "make_shader",
+            "make_pipeline",
+            "make_buffer",
+            "begin_default_pass(64, 64)",
+            "apply_pipeline",
+            "apply_bindings",
+            "draw(0, 3, 1)",
+            "end_pass",
+            "commit",
+        ]);
+
+        assert!(sg_uninstall_trace_hooks().is_some());
+
+        sg_commit();
+        assert_eq!(calls.borrow().len(), 9);
+    });
+}
+
+#[test]
+fn shutdown_drops_hooks() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    sg_run_headless(&SgDesc::default(), || {
+        sg_install_trace_hooks(Recorder {
+            calls: calls.clone(),
+        });
+    });
+
+    assert_eq!(Rc::strong_count(&calls), 1);
+
+    sg_run_headless(&SgDesc::default(), || {
+        assert!(sg_uninstall_trace_hooks().is_none());
+
+        sg_commit();
+        assert!(calls.borrow().is_empty());
+    });
+}
+
+#[test]
+fn stats_collector() {
+    sg_run_headless(&SgDesc::default(), || {
+        let stats = SgStatsCollector::new(2);
+        sg_install_trace_hooks(stats.clone());
+
+        let shd = make_shader();
+        let pip = make_pipeline(shd);
+        let buf = make_vertex_buffer();
+
+        draw_frame(pip, buf);
+        draw_frame(pip, buf);
+
+        let last = stats.last_frame();
+        assert_eq!(last.num_passes, 1);
+        assert_eq!(last.num_draws, 1);
+        assert_eq!(last.num_pipeline_switches, 1);
+        assert_eq!(last.num_apply_bindings, 1);
+        assert_eq!(stats.current_frame().num_draws, 0);
+
+        let average = stats.average();
+        assert_eq!(average.num_frames, 2);
+        assert_eq!(average.num_draws, 1.0);
+        assert_eq!(average.num_apply_bindings, 1.0);
+
+        // an empty frame pushes the first one out of the window
+        sg_commit();
+        assert_eq!(stats.last_frame().num_draws, 0);
+
+        let average = stats.average();
+        assert_eq!(average.num_frames, 2);
+        assert_eq!(average.num_passes, 0.5);
+        assert_eq!(average.num_draws, 0.5);
+
+        stats.reset();
+        assert_eq!(stats.average().num_frames, 0);
+    });
+}
+
+struct PanicOnCommit;
+
+impl SgTraceHooks for PanicOnCommit {
+    fn commit(&mut self) {
+        panic!("hook panic");
+    }
+}
+
+#[test]
+fn hook_panic_is_resumed() {
+    sg_run_headless(&SgDesc::default(), || {
+        sg_install_trace_hooks(PanicOnCommit);
+
+        assert!(panic::catch_unwind(sg_commit).is_err());
+
+        sg_uninstall_trace_hooks();
+    });
+}
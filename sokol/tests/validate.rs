@@ -0,0 +1,248 @@
+//! Checks the errors of the `validate()` functions and `sg_try_*()` wrappers with the
+//! dummy backend.
+//!
+//! ```sh
+//! cargo test -p sokol --test validate --features dummy
+//! ```
+
+use std::panic;
+
+use sokol::gfx::*;
+
+use common::{make_pipeline, make_shader, make_vertex_buffer};
+
+mod common;
+
+fn shader_desc<'a>(vs_uniform_blocks: Vec<SgShaderUniformBlockDesc<'a>>) -> SgShaderDesc<'a> {
+    SgShaderDesc {
+        attrs: vec![
+            SgShaderAttrDesc {
+                name: "position",
+                sem_name: "POSITION",
+                sem_index: 0,
+            },
+        ],
+        vs: SgShaderStageDesc {
+            source: Some("#version 330\nin vec4 position;\nvoid main() { gl_Position = position; }\n"),
+            uniform_blocks: vs_uniform_blocks,
+            ..Default::default()
+        },
+        fs: SgShaderStageDesc {
+            source: Some("#version 330\nout vec4 frag_color;\nvoid main() { frag_color = vec4(1.0); }\n"),
+            ..Default::default()
+        },
+        label: None,
+    }
+}
+
+fn pipeline_desc(shd: SgShader, attrs: Vec<SgVertexAttrDesc>) -> SgPipelineDesc<'static> {
+    SgPipelineDesc {
+        shader: shd,
+        layout: SgLayoutDesc {
+            attrs,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn float4_attr() -> SgVertexAttrDesc {
+    SgVertexAttrDesc {
+        format: SgVertexFormat::Float4,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn too_many() {
+    let action = SgPassAction {
+        colors: (0..5).map(|_| SgColorAttachmentAction::default()).collect(),
+        ..Default::default()
+    };
+    assert_eq!(action.validate(), Err(SgError::TooMany { what: "color attachments", count: 5, max: 4 }));
+
+    sg_run_headless(&SgDesc::default(), || {
+        let buf = make_vertex_buffer();
+
+        let result = sg_try_apply_bindings(&SgBindings {
+            vertex_buffers: vec![buf; 9],
+            ..Default::default()
+        });
+        assert_eq!(result, Err(SgError::TooMany { what: "vertex buffers", count: 9, max: 8 }));
+
+        let shd = make_shader();
+        let desc = pipeline_desc(shd, (0..17).map(|_| float4_attr()).collect());
+        assert_eq!(sg_try_make_pipeline(&desc),
+                   Err(SgError::TooMany { what: "vertex attributes", count: 17, max: 16 }));
+
+        // the plain function panics with the error, instead of indexing out of bounds
+        let err = panic::catch_unwind(|| sg_make_pipeline(&desc)).unwrap_err();
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.contains("17 vertex attributes"), "{}", message);
+        assert!(message.contains("sg_try_make_pipeline()"), "{}", message);
+    });
+}
+
+#[test]
+fn missing() {
+    assert_eq!(SgBufferDesc::default().validate(), Err(SgError::Missing { what: "buffer size" }));
+    assert_eq!(SgPassDesc::default().validate(), Err(SgError::Missing { what: "color attachments" }));
+
+    let mut desc = shader_desc(vec![]);
+    desc.fs.source = None;
+    assert_eq!(desc.validate(), Err(SgError::Missing { what: "fragment shader source or byte code" }));
+
+    let desc = pipeline_desc(SgShader::default(), vec![float4_attr()]);
+    assert_eq!(desc.validate(), Err(SgError::Missing { what: "shader" }));
+}
+
+#[test]
+fn vertex_layout() {
+    sg_run_headless(&SgDesc::default(), || {
+        let shd = make_shader();
+
+        let desc = pipeline_desc(shd, vec![float4_attr(), SgVertexAttrDesc::default()]);
+        assert_eq!(sg_try_make_pipeline(&desc), Err(SgError::VertexFormat { attr: 1 }));
+
+        let desc = pipeline_desc(shd, vec![SgVertexAttrDesc {
+            buffer_index: 8,
+            ..float4_attr()
+        }]);
+        assert_eq!(sg_try_make_pipeline(&desc), Err(SgError::VertexBufferIndex { attr: 0, buffer_index: 8 }));
+
+        let mut desc = pipeline_desc(shd, vec![float4_attr()]);
+        desc.layout.buffers = vec![SgBufferLayoutDesc {
+            stride: 6,
+            ..Default::default()
+        }];
+        assert_eq!(sg_try_make_pipeline(&desc), Err(SgError::VertexStride { buffer: 0, stride: 6 }));
+
+        let pip = sg_try_make_pipeline(&pipeline_desc(shd, vec![float4_attr()])).unwrap();
+        assert_eq!(sg_query_pipeline_state(pip), SgResourceState::Valid);
+    });
+}
+
+#[test]
+fn uniform_type() {
+    let desc = shader_desc(vec![
+        SgShaderUniformBlockDesc {
+            size: 16,
+            uniforms: vec![
+                SgShaderUniformDesc {
+                    name: "color",
+                    uniform_type: SgUniformType::_Invalid,
+                    array_count: 1,
+                },
+            ],
+            ..Default::default()
+        },
+    ]);
+
+    assert_eq!(desc.validate(),
+               Err(SgError::UniformType { stage: SgShaderStage::Vertex, ub_index: 0, uniform_index: 0 }));
+}
+
+#[test]
+fn image_size_and_content() {
+    assert_eq!(SgImageDesc::default().validate(), Err(SgError::ImageSize { width: 0, height: 0 }));
+
+    sg_run_headless(&SgDesc::default(), || {
+        let desc = SgImageDesc {
+            width: 4,
+            height: 4,
+            ..Default::default()
+        };
+
+        let pixels = [0u8; 4 * 4 * 3];
+        let result = sg_try_make_image(Some(&SgImageContent::new().mip(0, &pixels)), &desc);
+        assert_eq!(result, Err(SgError::ImageContent(SgImageContentError::Size {
+            face: 0,
+            mip: 0,
+            expected: 64,
+            actual: 48,
+        })));
+
+        let pixels = [0u8; 4 * 4 * 4];
+        let img = sg_try_make_image(Some(&SgImageContent::new().mip(0, &pixels)), &desc).unwrap();
+        assert_eq!(sg_query_image_state(img), SgResourceState::Valid);
+    });
+}
+
+#[test]
+fn buffer_size_and_native() {
+    sg_run_headless(&SgDesc::default(), || {
+        let result = sg_try_make_buffer_slice(&[0.0f32; 12], &SgBufferDesc {
+            size: 32,
+            ..Default::default()
+        });
+        assert_eq!(result, Err(SgError::BufferSize { size: 32, data_size: 48 }));
+
+        let result = sg_try_make_buffer_slice(&[0.0f32; 12], &SgBufferDesc {
+            native: Some(SgNativeBuffer::gl(1)),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(SgError::NativeBackend { resource: "buffers", backend: SgBackend::Dummy }));
+
+        let buf = sg_try_make_buffer_slice(&[0.0f32; 12], &SgBufferDesc::default()).unwrap();
+        assert_eq!(sg_query_buffer_state(buf), SgResourceState::Valid);
+    });
+}
+
+#[test]
+fn buffer_overflow() {
+    sg_run_headless(&SgDesc::default(), || {
+        let buf = sg_make_buffer(SG_BUFFER_CONTENT_NONE, &SgBufferDesc {
+            size: 32,
+            usage: SgUsage::Dynamic,
+            ..Default::default()
+        });
+
+        let err: SgError = sg_update_buffer_slice(buf, &[0u8; 64]).unwrap_err().into();
+        assert_eq!(err, SgError::BufferOverflow(SgBufferOverflow {
+            buffer: buf,
+            size: 64,
+            capacity: 32,
+        }));
+    });
+}
+
+#[test]
+fn failed() {
+    sg_run_headless(&SgDesc::default(), || {
+        // passes the Rust checks, but sokol_gfx requires uniform blocks in continuous slots
+        let desc = shader_desc(vec![
+            SgShaderUniformBlockDesc::default(),
+            SgShaderUniformBlockDesc {
+                size: 16,
+                uniforms: vec![
+                    SgShaderUniformDesc {
+                        name: "color",
+                        uniform_type: SgUniformType::Float4,
+                        array_count: 1,
+                    },
+                ],
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(desc.validate(), Ok(()));
+        assert_eq!(sg_try_make_shader(&desc), Err(SgError::Failed { resource: "shader" }));
+    });
+}
+
+#[test]
+fn invalid() {
+    let desc = SgDesc {
+        pipeline_pool_size: 1,
+        ..Default::default()
+    };
+
+    sg_run_headless(&desc, || {
+        let shd = make_shader();
+        let pip = make_pipeline(shd);
+        assert_eq!(sg_query_pipeline_state(pip), SgResourceState::Valid);
+
+        let desc = pipeline_desc(shd, vec![float4_attr()]);
+        assert_eq!(sg_try_make_pipeline(&desc), Err(SgError::Invalid { resource: "pipeline" }));
+    });
+}